@@ -0,0 +1,70 @@
+use chip_8::{Chip8, Config, ExecuteInstruction, Instruction};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+
+/// Draws font sprites across the screen while doing some arithmetic, clearing the display when done.
+const ROM: &[u8] = &[
+    0xA0, 0x50, // 0x200: Point I to the first font character
+    0x60, 0x00, // 0x202: Load 0 into V0
+    0x61, 0x00, // 0x204: Load 0 into V1
+    0xD0, 0x15, // 0x206: Draw 5 rows at V0, V1
+    0x70, 0x05, // 0x208: Add 5 to V0
+    0x71, 0x03, // 0x20A: Add 3 to V1
+    0x80, 0x14, // 0x20C: Add V1 to V0
+    0x82, 0x06, // 0x20E: Shift V2 right
+    0x30, 0xFF, // 0x210: Skip if V0 is 0xFF
+    0x12, 0x06, // 0x212: Jump to the draw
+    0x00, 0xE0, // 0x214: Clear the display
+    0x12, 0x02, // 0x216: Start over
+];
+
+const INSTRUCTIONS: usize = 6000;
+const INSTRUCTIONS_PER_FRAME: usize = 10;
+
+fn advance_instruction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("advance_instruction");
+    group.throughput(Throughput::Elements(INSTRUCTIONS as u64));
+
+    for (name, config) in [
+        ("modern", Config::modern()),
+        ("cosmac_vip", Config::cosmac_vip()),
+        ("chip48", Config::chip48()),
+    ] {
+        let mut chip = Chip8::new(config);
+        chip.load(ROM);
+
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || chip.clone(),
+                |chip| {
+                    for _ in 0..INSTRUCTIONS / INSTRUCTIONS_PER_FRAME {
+                        chip.advance_frame(INSTRUCTIONS_PER_FRAME)
+                            .expect("benchmark ROM is valid");
+                    }
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+fn display_draw(c: &mut Criterion) {
+    let mut chip = Chip8::default();
+    chip.load(ROM);
+    chip.advance_instruction().expect("benchmark ROM is valid");
+
+    c.bench_function("display_draw", |b| {
+        b.iter(|| {
+            chip.execute(black_box(&Instruction::DisplayDraw {
+                vx: 0,
+                vy: 1,
+                height: 15,
+            }))
+            .expect("draw is valid");
+        });
+    });
+}
+
+criterion_group!(benches, advance_instruction, display_draw);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+#![no_main]
+
+use chip_8::{Instruction, Opcode, ParseError};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|opcode: u16| {
+    match Instruction::try_from(Opcode::from(opcode)) {
+        Ok(instruction) => {
+            for register in instruction.registers().into_iter().flatten() {
+                assert!(register <= 0xF, "{instruction:?} uses register {register}");
+            }
+        }
+        Err(ParseError::UnknownOpcode(unknown) | ParseError::MissingOperand(unknown)) => {
+            assert_eq!(unknown, Opcode::from(opcode));
+        }
+    }
+});
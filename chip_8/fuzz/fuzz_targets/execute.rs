@@ -0,0 +1,43 @@
+#![no_main]
+
+use chip_8::{Chip8, Config, IndexOverflow, Memory};
+use libfuzzer_sys::fuzz_target;
+
+const FRAMES: usize = 60;
+const INSTRUCTIONS_PER_FRAME: usize = 10;
+
+fuzz_target!(|data: &[u8]| {
+    // First two bytes pick the quirks, the rest is the ROM.
+    let [low, high, rom @ ..] = data else {
+        return;
+    };
+    let quirks = u16::from_le_bytes([*low, *high]);
+    let rom = &rom[..rom
+        .len()
+        .min(Memory::SIZE_RAM - Memory::INDEX_PROGRAM_START as usize)];
+
+    let mut chip = Chip8::new(Config {
+        shift_ignores_vy: quirks & 1 << 0 != 0,
+        logic_resets_vf: quirks & 1 << 8 != 0,
+        jump_reads_from_vx: quirks & 1 << 1 != 0,
+        add_to_index_stores_overflow: quirks & 1 << 2 != 0,
+        index_overflow: match (quirks >> 6) & 0b11 {
+            0 => IndexOverflow::Full,
+            1 => IndexOverflow::Wrap,
+            _ => IndexOverflow::Saturate,
+        },
+        store_load_modifies_i: quirks & 1 << 3 != 0,
+        display_wait: quirks & 1 << 4 != 0,
+        wait_key_on_release: quirks & 1 << 5 != 0,
+        wait_key_timeout_frames: None,
+        wait_key_timeout_key: 0,
+        program_start: Memory::INDEX_PROGRAM_START,
+    });
+    chip.load(rom);
+
+    for frame in 0..FRAMES {
+        // Errors are fine, panics are not.
+        let _ = chip.advance_frame(INSTRUCTIONS_PER_FRAME);
+        chip.set_keys(std::array::from_fn(|key| key == frame % Memory::SIZE_KEYS));
+    }
+});
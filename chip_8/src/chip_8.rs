@@ -1,9 +1,35 @@
+mod addr;
+mod breakpoint;
+mod builder;
+mod checksum;
 mod config;
+mod display;
+mod events;
+mod history;
 mod memory;
+mod rng;
+#[cfg(feature = "serde")]
+mod serde_array;
 mod system;
+mod watch;
 
-pub use config::Config;
+pub use addr::Addr;
+pub(crate) use breakpoint::Breakpoints;
+pub use builder::Chip8Builder;
+pub use config::{Config, IndexOverflow};
+pub use display::{Display, Vram};
+pub use events::Chip8Event;
+pub(crate) use events::Events;
+pub(crate) use history::History;
 pub use memory::Memory;
+pub(crate) use rng::Rng;
+pub use system::Checkpoint;
 pub use system::Chip8;
+pub use system::DrawStats;
 pub use system::InstructionError;
+pub use system::LoadError;
+pub use system::RunOutcome;
 pub(crate) use system::State;
+pub use system::StepOutcome;
+pub use watch::Watch;
+pub(crate) use watch::Watches;
@@ -0,0 +1,48 @@
+/// Address in RAM, like the ones in the program counter and the index register.
+///
+/// Can point past the end of RAM, [`Addr::masked`] turns it into an index that can not.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Addr(pub u16);
+
+impl Addr {
+    /// Index in RAM of the address, wrapping around to the start of RAM past its end.
+    ///
+    /// # Arguments
+    ///
+    /// * `ram_size` - Length of RAM, must not be 0.
+    pub fn masked(self, ram_size: usize) -> usize {
+        self.0 as usize % ram_size
+    }
+}
+
+impl From<u16> for Addr {
+    fn from(address: u16) -> Self {
+        Self(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    #[case(0x200, 0x1000, 0x200)]
+    #[case(0xFFF, 0x1000, 0xFFF)]
+    #[case(0x1000, 0x1000, 0x000)]
+    #[case(0xFFFF, 0x1000, 0xFFF)]
+    #[case(0xFFFF, 0x10000, 0xFFFF)]
+    #[case(0x300, 0x300, 0x000)]
+    #[case(0x301, 0x300, 0x001)]
+    fn masked_stays_in_ram(
+        #[case] address: u16,
+        #[case] ram_size: usize,
+        #[case] index: usize,
+    ) -> Result<()> {
+        assert_eq!(Addr(address).masked(ram_size), index);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Registered breakpoints, see [`crate::Chip8::add_breakpoint`].
+///
+/// Not a part of the emulated machine, so it is ignored by serialization.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct Breakpoints(Vec<(u16, bool)>);
+
+impl Breakpoints {
+    /// Register a breakpoint, a one-shot one stays one-shot only if it was not already registered as permanent.
+    pub(crate) fn add(&mut self, address: u16, one_shot: bool) {
+        match self.0.iter_mut().find(|(a, _)| *a == address) {
+            Some((_, existing)) => *existing &= one_shot,
+            None => self.0.push((address, one_shot)),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, address: u16) {
+        self.0.retain(|&(a, _)| a != address);
+    }
+
+    /// Whether the execution should stop at an address, forgetting the breakpoint if it was one-shot.
+    pub(crate) fn hit(&mut self, address: u16) -> bool {
+        let Some(&(_, one_shot)) = self.0.iter().find(|&&(a, _)| a == address) else {
+            return false;
+        };
+        if one_shot {
+            self.remove(address);
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn hit_removes_one_shot() -> Result<()> {
+        let mut target = Breakpoints::default();
+        target.add(0x204, true);
+        target.add(0x208, false);
+
+        assert!(!target.hit(0x200));
+        assert!(target.hit(0x204));
+        assert!(!target.hit(0x204));
+        assert!(target.hit(0x208));
+        assert!(target.hit(0x208));
+        assert_eq!(target, Breakpoints(vec![(0x208, false)]));
+        Ok(())
+    }
+
+    #[rstest]
+    fn add_keeps_permanent(#[values(true, false)] one_shot_first: bool) -> Result<()> {
+        let mut target = Breakpoints::default();
+
+        target.add(0x204, one_shot_first);
+        target.add(0x204, !one_shot_first);
+
+        assert_eq!(target, Breakpoints(vec![(0x204, false)]));
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::*;
+
+/// Configure a [`Chip8`] in one place, see [`Chip8::builder`].
+///
+/// Everything that is not set keeps the value [`Chip8::new`] would give it.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Chip8Builder<'a> {
+    config: Config,
+    ram_size: Option<usize>,
+    seed: Option<u64>,
+    font: Option<[[u8; 5]; 16]>,
+    rom: &'a [u8],
+    history: usize,
+    breakpoints: Vec<u16>,
+    #[cfg(feature = "std")]
+    events: Option<std::sync::mpsc::Sender<Chip8Event>>,
+}
+
+impl<'a> Chip8Builder<'a> {
+    /// Emulation quirks, [`Config::default`] if not set.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Length of RAM in bytes, see [`Chip8::with_ram_size`].
+    pub fn ram_size(mut self, size: usize) -> Self {
+        self.ram_size = Some(size);
+        self
+    }
+
+    /// Seed of the random numbers generated by `Cxnn`, so runs can be repeated.
+    pub fn rng(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Font that `Fx29` points to instead of [`Memory::FONT`].
+    pub fn font(mut self, font: [[u8; 5]; 16]) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Program to load.
+    pub fn rom(mut self, rom: &'a [u8]) -> Self {
+        self.rom = rom;
+        self
+    }
+
+    /// How many executed instructions to record, see [`Chip8::with_history`].
+    pub fn history(mut self, capacity: usize) -> Self {
+        self.history = capacity;
+        self
+    }
+
+    /// Stop at an address, see [`Chip8::add_breakpoint`]. Can be repeated.
+    pub fn breakpoint(mut self, address: u16) -> Self {
+        self.breakpoints.push(address);
+        self
+    }
+
+    /// Send notable events to a channel, see [`Chip8::set_events`].
+    #[cfg(feature = "std")]
+    pub fn events(mut self, sender: std::sync::mpsc::Sender<Chip8Event>) -> Self {
+        self.events = Some(sender);
+        self
+    }
+
+    /// Create the machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if the RAM size is invalid or the ROM does not fit into RAM.
+    pub fn build(self) -> Result<Chip8, LoadError> {
+        let mut chip = Chip8::new(self.config).with_history(self.history);
+        if let Some(size) = self.ram_size {
+            chip = chip.with_ram_size(size)?;
+        }
+        if let Some(seed) = self.seed {
+            chip.rng = Rng::from_seed(seed);
+        }
+
+        chip.try_load(self.rom)?;
+        // Loading brings back the built-in font.
+        if let Some(font) = &self.font {
+            chip.set_font(font);
+        }
+
+        for address in self.breakpoints {
+            chip.add_breakpoint(address, false);
+        }
+        #[cfg(feature = "std")]
+        chip.set_events(self.events);
+
+        Ok(chip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn build_default_matches_new() -> Result<()> {
+        assert_eq!(Chip8::builder().build()?, Chip8::default());
+        Ok(())
+    }
+
+    #[rstest]
+    fn build_with_config_and_rom() -> Result<()> {
+        let rom = [
+            0x61, 0x02, // Load 2 into register 1
+            0xF1, 0x29, // Point I at the character in register 1
+            0xC2, 0xFF, // Load a random number into register 2
+        ];
+        let font = [[0xAA; 5]; 16];
+
+        let mut target = Chip8::builder()
+            .config(Config::cosmac_vip())
+            .rom(&rom)
+            .font(font)
+            .rng(42)
+            .history(2)
+            .breakpoint(Memory::INDEX_PROGRAM_START + 4)
+            .build()?;
+
+        let mut result = Chip8::from_rom(&rom, Config::cosmac_vip())?;
+        result.set_font(&font);
+
+        assert_eq!(target.memory(), result.memory());
+        assert_eq!(
+            target.tick_instructions(3)?,
+            StepOutcome::BreakpointHit(Memory::INDEX_PROGRAM_START + 4)
+        );
+        assert_eq!(target.peek(target.i()), Some(0xAA));
+        assert_eq!(target.history().len(), 2);
+
+        target.tick_instructions(1)?;
+        let random = target.register(0x2);
+        let mut repeated = Chip8::builder().rom(&rom).rng(42).build()?;
+        repeated.tick_instructions(3)?;
+
+        assert_eq!(repeated.register(0x2), random);
+        Ok(())
+    }
+
+    #[rstest]
+    fn build_with_same_seed_repeats_random_numbers() -> Result<()> {
+        let rom = [
+            0xC0, 0xFF, // Load a random number into register 0
+            0xC1, 0xFF, // Load a random number into register 1
+            0xC2, 0x0F, // Load a random number into register 2
+        ];
+        let mut target = Chip8::builder().rom(&rom).rng(42).build()?;
+        let mut result = Chip8::builder().rom(&rom).rng(42).build()?;
+
+        target.tick_instructions(3)?;
+        result.tick_instructions(3)?;
+
+        assert_eq!(target.memory(), result.memory());
+        Ok(())
+    }
+
+    #[rstest]
+    fn build_oversized_rom() -> Result<()> {
+        let rom = [0; 0x201];
+
+        assert_eq!(
+            Chip8::builder().ram_size(0x400).rom(&rom).build(),
+            Err(LoadError::OutOfBounds {
+                address: Memory::INDEX_PROGRAM_START,
+                len: rom.len(),
+            })
+        );
+        assert_eq!(
+            Chip8::builder().ram_size(0).build(),
+            Err(LoadError::InvalidRamSize(0))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+/// 64-bit FNV-1a hash, fast and good enough to tell states apart.
+///
+/// Not suitable against deliberate collisions.
+pub(crate) fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01B3;
+
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    #[case(b"", 0xCBF2_9CE4_8422_2325)]
+    #[case(b"a", 0xAF63_DC4C_8601_EC8C)]
+    #[case(b"foobar", 0x8594_4171_F739_67E8)]
+    fn fnv1a_matches_reference(#[case] bytes: &[u8], #[case] hash: u64) -> Result<()> {
+        assert_eq!(fnv1a(bytes.iter().copied()), hash);
+        Ok(())
+    }
+}
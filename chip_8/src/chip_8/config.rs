@@ -1,5 +1,44 @@
+use super::Memory;
+
+/// What `Fx1E` does with `I` when the sum goes past the end of RAM.
+///
+/// Whichever is chosen, instructions that access RAM through `I` wrap around to its start,
+/// so this only matters for the value a program reads back from `I`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum IndexOverflow {
+    /// Keep the whole sum, wrapping around only after `0xFFFF` like a 16-bit register.
+    #[default]
+    Full,
+    /// Wrap around to the start of RAM, so `I` is always a valid address.
+    Wrap,
+    /// Stop at the last byte of RAM.
+    Saturate,
+}
+
+impl IndexOverflow {
+    /// Add an offset to `I` following this behavior.
+    ///
+    /// # Arguments
+    ///
+    /// * `i` - Value of the index register.
+    /// * `offset` - Value to add.
+    /// * `ram_size` - Length of RAM.
+    pub fn add(self, i: u16, offset: u16, ram_size: usize) -> u16 {
+        let sum = i as usize + offset as usize;
+        match self {
+            IndexOverflow::Full => i.wrapping_add(offset),
+            IndexOverflow::Wrap => (sum % ram_size) as u16,
+            IndexOverflow::Saturate => sum.min(ram_size - 1) as u16,
+        }
+    }
+}
+
 /// Emulation compatibility configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct Config {
     /// Original interpreters copied `Vy` into `Vx` before byte shifting.
     /// Newer implementations shift Vx in place.
@@ -10,21 +49,40 @@ pub struct Config {
     /// * `8xy6`
     /// * `8xyE`
     pub shift_ignores_vy: bool,
+    /// Original interpreters reset `VF` to 0 as a side effect of the logical operations.
+    /// Newer implementations leave it as is.
+    ///
+    /// `false` is most compatible.
+    ///
+    /// Affected instructions:
+    /// * `8xy1`
+    /// * `8xy2`
+    /// * `8xy3`
+    pub logic_resets_vf: bool,
     /// Original interpreters used `V0` for offset.
-    /// Newer implementations use erroneously `Vx`.
+    /// Newer implementations use erroneously `Vx`,
+    /// where `x` is the high nibble of the address, so `Bxnn` jumps to `xnn + Vx`.
     ///
     /// `false` is most compatible.
     ///
     /// Affected instructions:
     /// * `Bnnn`
     pub jump_reads_from_vx: bool,
-    /// Newer implementations store an overflow flag when the memory pointer register is outside the valid range.
+    /// Newer implementations store an overflow flag when the memory pointer register goes past the end of RAM.
     ///
     /// `true` is most compatible.
     ///
     /// Affected instructions:
     /// * `Fx1E`
     pub add_to_index_stores_overflow: bool,
+    /// The original interpreter kept `I` in a 16-bit register.
+    /// Other implementations keep it inside RAM, see [`IndexOverflow`].
+    ///
+    /// [`IndexOverflow::Full`] is most compatible.
+    ///
+    /// Affected instructions:
+    /// * `Fx1E`
+    pub index_overflow: IndexOverflow,
     /// Original interpreters incremented `I` while storing and loading memory from RAM.
     /// Newer implementations do it in place without modifying `I`.
     ///
@@ -34,15 +92,216 @@ pub struct Config {
     /// * `Fx55`
     /// * `Fx65`
     pub store_load_modifies_i: bool,
+    /// Original interpreters waited for the vertical blank interrupt after drawing a sprite,
+    /// so at most one sprite was drawn per frame.
+    /// Newer implementations draw immediately without stalling.
+    ///
+    /// `false` is most compatible.
+    ///
+    /// Affected instructions:
+    /// * `Dxyn`
+    pub display_wait: bool,
+    /// Original interpreters stored the key after it was released.
+    /// Some newer implementations store it as soon as it is pressed.
+    ///
+    /// `true` is most compatible.
+    ///
+    /// Affected instructions:
+    /// * `Fx0A`
+    pub wait_key_on_release: bool,
+    /// Not a quirk of any interpreter, gives up waiting for a key after this many timer updates
+    /// and stores [`Config::wait_key_timeout_key`] instead, so a program that waits forever does not look frozen.
+    ///
+    /// `None` waits forever and is most compatible.
+    ///
+    /// Affected instructions:
+    /// * `Fx0A`
+    pub wait_key_timeout_frames: Option<u32>,
+    /// Key stored when waiting runs out, see [`Config::wait_key_timeout_frames`].
+    ///
+    /// Must be in `0..=0xF` like any key `Fx0A` could store, larger values are rejected when deserializing
+    /// and only their low nibble is stored otherwise.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_key"))]
+    pub wait_key_timeout_key: u8,
+    /// Address where the ROM is loaded and execution starts.
+    /// Some variants, like the ETI-660, start at `0x600` instead.
+    ///
+    /// [`Memory::INDEX_PROGRAM_START`] is most compatible.
+    pub program_start: u16,
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_key<'de, D>(deserializer: D) -> Result<u8, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    let key = u8::deserialize(deserializer)?;
+    if (key as usize) < Memory::SIZE_KEYS {
+        Ok(key)
+    } else {
+        Err(D::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(key.into()),
+            &"a key in 0-F range",
+        ))
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
+        Self::modern()
+    }
+}
+
+impl Config {
+    /// Behavior of the original interpreter on the COSMAC VIP.
+    pub fn cosmac_vip() -> Self {
+        Self {
+            shift_ignores_vy: false,
+            logic_resets_vf: true,
+            jump_reads_from_vx: false,
+            add_to_index_stores_overflow: false,
+            index_overflow: IndexOverflow::Full,
+            store_load_modifies_i: true,
+            display_wait: true,
+            wait_key_on_release: true,
+            wait_key_timeout_frames: None,
+            wait_key_timeout_key: 0,
+            program_start: Memory::INDEX_PROGRAM_START,
+        }
+    }
+
+    /// Behavior of CHIP-48 on the HP-48 calculators.
+    pub fn chip48() -> Self {
         Self {
             shift_ignores_vy: true,
+            logic_resets_vf: false,
+            jump_reads_from_vx: true,
+            add_to_index_stores_overflow: false,
+            index_overflow: IndexOverflow::Full,
+            store_load_modifies_i: false,
+            display_wait: false,
+            wait_key_on_release: true,
+            wait_key_timeout_frames: None,
+            wait_key_timeout_key: 0,
+            program_start: Memory::INDEX_PROGRAM_START,
+        }
+    }
+
+    /// Behavior of SUPER-CHIP 1.1 on the HP-48 calculators.
+    pub fn superchip() -> Self {
+        Self {
+            shift_ignores_vy: true,
+            logic_resets_vf: false,
+            jump_reads_from_vx: true,
+            add_to_index_stores_overflow: false,
+            index_overflow: IndexOverflow::Full,
+            store_load_modifies_i: false,
+            display_wait: false,
+            wait_key_on_release: true,
+            wait_key_timeout_frames: None,
+            wait_key_timeout_key: 0,
+            program_start: Memory::INDEX_PROGRAM_START,
+        }
+    }
+
+    /// Behavior most ROMs written for modern interpreters expect.
+    pub fn modern() -> Self {
+        Self {
+            shift_ignores_vy: true,
+            logic_resets_vf: false,
             jump_reads_from_vx: false,
             add_to_index_stores_overflow: true,
+            index_overflow: IndexOverflow::Full,
             store_load_modifies_i: false,
+            display_wait: false,
+            wait_key_on_release: true,
+            wait_key_timeout_frames: None,
+            wait_key_timeout_key: 0,
+            program_start: Memory::INDEX_PROGRAM_START,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn default_is_modern() -> Result<()> {
+        assert_eq!(Config::default(), Config::modern());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(IndexOverflow::Full, 0xFFF, 1, 0x1000)]
+    #[case(IndexOverflow::Full, 0xFFFF, 2, 0x0001)]
+    #[case(IndexOverflow::Wrap, 0xFFF, 1, 0x000)]
+    #[case(IndexOverflow::Wrap, 0xFFF, 0xFF, 0x0FE)]
+    #[case(IndexOverflow::Wrap, 0xFFFF, 2, 0x001)]
+    #[case(IndexOverflow::Saturate, 0xFFF, 1, 0xFFF)]
+    #[case(IndexOverflow::Saturate, 0xFFFF, 2, 0xFFF)]
+    #[case(IndexOverflow::Saturate, 0xF00, 0xFF, 0xFFF)]
+    fn index_overflow_add(
+        #[case] target: IndexOverflow,
+        #[case] i: u16,
+        #[case] offset: u16,
+        #[case] result: u16,
+    ) -> Result<()> {
+        assert_eq!(target.add(i, offset, 0x1000), result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn index_overflow_add_in_range(
+        #[values(IndexOverflow::Full, IndexOverflow::Wrap, IndexOverflow::Saturate)]
+        target: IndexOverflow,
+    ) -> Result<()> {
+        assert_eq!(target.add(0x300, 0xFF, 0x1000), 0x3FF);
+        assert_eq!(target.add(0x300, 0xFF, 0x10000), 0x3FF);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(IndexOverflow::Full, 0xFFF, 1, 0x1000)]
+    #[case(IndexOverflow::Wrap, 0xFFF, 1, 0x1000)]
+    #[case(IndexOverflow::Saturate, 0xFFF, 1, 0x1000)]
+    #[case(IndexOverflow::Full, 0xFFFF, 1, 0x0000)]
+    #[case(IndexOverflow::Wrap, 0xFFFF, 1, 0x0000)]
+    #[case(IndexOverflow::Saturate, 0xFFFF, 1, 0xFFFF)]
+    fn index_overflow_add_large_ram(
+        #[case] target: IndexOverflow,
+        #[case] i: u16,
+        #[case] offset: u16,
+        #[case] result: u16,
+    ) -> Result<()> {
+        assert_eq!(target.add(i, offset, 0x10000), result);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::cosmac_vip(Config::cosmac_vip(), [false, true, false, false, true, true, true])]
+    #[case::chip48(Config::chip48(), [true, false, true, false, false, false, true])]
+    #[case::superchip(Config::superchip(), [true, false, true, false, false, false, true])]
+    #[case::modern(Config::modern(), [true, false, false, true, false, false, true])]
+    fn presets(#[case] target: Config, #[case] flags: [bool; 7]) -> Result<()> {
+        assert_eq!(
+            [
+                target.shift_ignores_vy,
+                target.logic_resets_vf,
+                target.jump_reads_from_vx,
+                target.add_to_index_stores_overflow,
+                target.store_load_modifies_i,
+                target.display_wait,
+                target.wait_key_on_release,
+            ],
+            flags
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+use core::ops::{Index, IndexMut};
+
+use super::Memory;
+
+/// Display of the original CHIP-8, 64x32 pixels.
+pub type Vram = Display<{ Memory::SIZE_DISPLAY_WIDTH }, { Memory::SIZE_DISPLAY_HEIGHT }>;
+
+/// Monochrome display of `W` by `H` pixels.
+///
+/// Stored one row per element, with the leftmost pixel in bit `W - 1` and the rightmost one in bit 0.
+/// Supports widths from 8 to 128 pixels, so both the 64x32 and the 128x64 modes fit.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Display<const W: usize, const H: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_array"))]
+    rows: [u128; H],
+}
+
+impl<const W: usize, const H: usize> Display<W, H> {
+    pub const WIDTH: usize = W;
+    pub const HEIGHT: usize = H;
+    /// Row with every pixel on.
+    pub const FULL_ROW: u128 = u128::MAX >> (u128::BITS as usize - W);
+
+    const VALID_WIDTH: () = assert!(
+        W >= 8 && W <= u128::BITS as usize,
+        "display width must be between 8 and 128"
+    );
+
+    pub fn new() -> Self {
+        let () = Self::VALID_WIDTH;
+        Self { rows: [0; H] }
+    }
+
+    /// Bit of a row that holds the pixel in a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the pixel, must be less than the display width.
+    pub const fn pixel_mask(x: usize) -> u128 {
+        1 << (W - 1 - x)
+    }
+
+    /// All rows, from top to bottom.
+    pub fn rows(&self) -> &[u128; H] {
+        &self.rows
+    }
+
+    /// If the pixel at a position is on.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the pixel, must be less than the display width.
+    /// * `y` - Row of the pixel, must be less than the display height.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        self.rows[y] & Self::pixel_mask(x) != 0
+    }
+
+    /// Turn a pixel on or off.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the pixel, must be less than the display width.
+    /// * `y` - Row of the pixel, must be less than the display height.
+    /// * `on` - Whether the pixel should be lit.
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) {
+        if on {
+            self.rows[y] |= Self::pixel_mask(x);
+        } else {
+            self.rows[y] &= !Self::pixel_mask(x);
+        }
+    }
+
+    /// Flip the pixels of a sprite, like `Dxyn`.
+    ///
+    /// The start position wraps around the display, but the pixels that go past its edges are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the left edge of the sprite.
+    /// * `y` - Row of the top edge of the sprite.
+    /// * `sprite` - Rows of the sprite, 8 pixels each with the leftmost one in the most significant bit.
+    ///
+    /// # Returns
+    ///
+    /// Whether any pixel was turned off.
+    pub fn draw_sprite(&mut self, x: usize, y: usize, sprite: &[u8]) -> bool {
+        let x = x % W;
+        let y = y % H;
+
+        let mut collision = false;
+        for (row, &byte) in self.rows[y..].iter_mut().zip(sprite) {
+            // Line the sprite up with the left edge, then shift it into place.
+            // Pixels pushed past the right edge fall off.
+            let bits = (u128::from(byte) << (W - 8)) >> x;
+            collision |= *row & bits != 0;
+            *row ^= bits;
+        }
+
+        collision
+    }
+
+    /// Turn every pixel off.
+    pub fn clear(&mut self) {
+        self.rows = [0; H];
+    }
+
+    /// State of each pixel, unpacked.
+    ///
+    /// Stored in `[y][x]` format.
+    pub fn frame_buffer(&self) -> [[bool; W]; H] {
+        core::array::from_fn(|y| core::array::from_fn(|x| self.pixel(x, y)))
+    }
+
+    /// Coordinates of every pixel that is on, in `(x, y)` format.
+    ///
+    /// Goes row by row, from left to right.
+    pub fn lit_pixels(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..H).flat_map(move |y| {
+            (0..W)
+                .filter(move |&x| self.pixel(x, y))
+                .map(move |x| (x, y))
+        })
+    }
+}
+
+impl<const W: usize, const H: usize> Default for Display<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Raw access to a row, bits past the display width must stay 0.
+impl<const W: usize, const H: usize> Index<usize> for Display<W, H> {
+    type Output = u128;
+
+    fn index(&self, y: usize) -> &Self::Output {
+        &self.rows[y]
+    }
+}
+
+impl<const W: usize, const H: usize> IndexMut<usize> for Display<W, H> {
+    fn index_mut(&mut self, y: usize) -> &mut Self::Output {
+        &mut self.rows[y]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn full_row_covers_width() -> Result<()> {
+        assert_eq!(Display::<64, 32>::FULL_ROW, u128::from(u64::MAX));
+        assert_eq!(Display::<128, 64>::FULL_ROW, u128::MAX);
+        Ok(())
+    }
+
+    #[rstest]
+    fn lit_pixels_yields_sprite() -> Result<()> {
+        let mut target = Vram::new();
+        // Font glyph `1` at (10, 20)
+        for (dy, &row) in Memory::FONT[1].iter().enumerate() {
+            target[20 + dy] = u128::from(row) << (Vram::WIDTH - 8 - 10);
+        }
+
+        assert_eq!(
+            target.lit_pixels().collect::<Vec<_>>(),
+            vec![
+                (12, 20),
+                (11, 21),
+                (12, 21),
+                (12, 22),
+                (12, 23),
+                (11, 24),
+                (12, 24),
+                (13, 24),
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn pixel_reads_bit(
+        #[values(0, 1, 31, 62, 63)] x: usize,
+        #[values(0, 1, 31)] y: usize,
+    ) -> Result<()> {
+        let mut target = Vram::new();
+        target[y] = Vram::pixel_mask(x);
+
+        assert!(target.pixel(x, y));
+        assert_eq!(target.lit_pixels().collect::<Vec<_>>(), vec![(x, y)]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_pixel_toggles_single_bit() -> Result<()> {
+        let mut target = Vram::new();
+        target[3] = Vram::FULL_ROW;
+
+        target.set_pixel(10, 3, false);
+        target.set_pixel(0, 4, true);
+
+        assert_eq!(target[3], Vram::FULL_ROW & !Vram::pixel_mask(10));
+        assert_eq!(target[4], Vram::pixel_mask(0));
+        Ok(())
+    }
+
+    #[rstest]
+    fn frame_buffer_unpacks_rows() -> Result<()> {
+        let mut target = Vram::new();
+        target[0] = Vram::FULL_ROW;
+        target[1] = 0b101 << (Vram::WIDTH - 3);
+
+        let mut result = [[false; Vram::WIDTH]; Vram::HEIGHT];
+        result[0] = [true; Vram::WIDTH];
+        result[1][0] = true;
+        result[1][2] = true;
+
+        assert_eq!(target.frame_buffer(), result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn lit_pixels_empty() -> Result<()> {
+        let target = Vram::new();
+
+        assert_eq!(target.lit_pixels().next(), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn clear_resets() -> Result<()> {
+        let mut target = Vram::new();
+        target[0] = Vram::FULL_ROW;
+
+        target.clear();
+
+        assert_eq!(target, Vram::new());
+        Ok(())
+    }
+
+    #[rstest]
+    fn draw_sprite_low_res() -> Result<()> {
+        let mut target = Display::<64, 32>::new();
+
+        let collision = target.draw_sprite(62, 31, &[0b11100000, 0b11100000]);
+
+        assert!(!collision);
+        assert_eq!(
+            target.lit_pixels().collect::<Vec<_>>(),
+            vec![(62, 31), (63, 31)]
+        );
+
+        let collision = target.draw_sprite(62 + 64, 31 + 32, &[0b10000000]);
+
+        assert!(collision);
+        assert_eq!(target.lit_pixels().collect::<Vec<_>>(), vec![(63, 31)]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn draw_sprite_high_res() -> Result<()> {
+        let mut target = Display::<128, 64>::new();
+
+        let collision = target.draw_sprite(100, 40, &[0b10000001]);
+
+        assert!(!collision);
+        assert_eq!(
+            target.lit_pixels().collect::<Vec<_>>(),
+            vec![(100, 40), (107, 40)]
+        );
+
+        target.draw_sprite(126, 63, &[0b11100000, 0b11100000]);
+
+        assert_eq!(
+            target.lit_pixels().collect::<Vec<_>>(),
+            vec![(100, 40), (107, 40), (126, 63), (127, 63)]
+        );
+        assert_eq!(target.frame_buffer()[63][127], true);
+        Ok(())
+    }
+
+    #[rstest]
+    fn draw_sprite_same_on_both_sizes_away_from_edges(
+        #[values(0, 5, 40)] x: usize,
+        #[values(0, 7, 20)] y: usize,
+    ) -> Result<()> {
+        let sprite = Memory::FONT[0xA];
+        let mut low = Display::<64, 32>::new();
+        let mut high = Display::<128, 64>::new();
+
+        low.draw_sprite(x, y, &sprite);
+        high.draw_sprite(x, y, &sprite);
+
+        assert_eq!(
+            low.lit_pixels().collect::<Vec<_>>(),
+            high.lit_pixels().collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(feature = "std")]
+use std::sync::mpsc::Sender;
+
+/// Notable things that happened while emulating.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Chip8Event {
+    /// `00E0` was executed.
+    DisplayCleared,
+    /// `Dxyn` was executed.
+    SpriteDrawn {
+        /// Whether the sprite turned off a pixel.
+        collision: bool,
+    },
+    /// The sound timer was started.
+    SoundStart,
+    /// The sound timer ran out or was stopped.
+    SoundStop,
+    /// `Fx0A` blocked the execution until a key is released.
+    WaitingForKey,
+    /// A subroutine call nested deeper than [`crate::Memory::SIZE_STACK`].
+    StackOverflow,
+}
+
+/// Optional receiver of [`Chip8Event`].
+///
+/// Not a part of the emulated machine, so it is ignored by comparisons and serialization.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Events {
+    #[cfg(feature = "std")]
+    pub(crate) sender: Option<Sender<Chip8Event>>,
+}
+
+impl Events {
+    /// Notify the receiver, if there is one and it is still listening.
+    #[cfg_attr(not(feature = "std"), allow(clippy::unused_self))]
+    pub(crate) fn emit(&self, event: Chip8Event) {
+        #[cfg(feature = "std")]
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(event);
+        }
+    }
+}
+
+impl PartialEq for Events {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Events {}
@@ -0,0 +1,74 @@
+use alloc::collections::VecDeque;
+
+use crate::instruction::Instruction;
+
+/// Most recently executed instructions with their addresses, see [`crate::Chip8::with_history`].
+///
+/// Not a part of the emulated machine, so it is ignored by serialization.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct History {
+    entries: VecDeque<(u16, Instruction)>,
+    /// How many entries are kept, 0 disables recording.
+    capacity: usize,
+}
+
+impl History {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Remember an instruction, forgetting the oldest one when full.
+    pub(crate) fn push(&mut self, address: u16, instruction: Instruction) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((address, instruction));
+    }
+
+    pub(crate) fn entries(&self) -> &VecDeque<(u16, Instruction)> {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn push_drops_oldest() -> Result<()> {
+        let mut history = History::new(2);
+
+        for address in [0x200, 0x202, 0x204] {
+            history.push(address, Instruction::DisplayClear);
+        }
+
+        assert_eq!(
+            history.entries(),
+            &VecDeque::from([
+                (0x202, Instruction::DisplayClear),
+                (0x204, Instruction::DisplayClear)
+            ])
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn push_disabled() -> Result<()> {
+        let mut history = History::default();
+
+        history.push(0x200, Instruction::DisplayClear);
+
+        assert!(history.entries().is_empty());
+        Ok(())
+    }
+}
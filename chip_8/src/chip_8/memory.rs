@@ -1,3 +1,9 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::{Addr, Vram};
+use crate::instruction::Opcode;
+
 const FONT: [[u8; 5]; 16] = [
     [
         0b11110000, // ####
@@ -114,19 +120,218 @@ const FONT: [[u8; 5]; 16] = [
     ],
 ];
 
+/// SUPER-CHIP font for the large characters of the high resolution mode.
+const FONT_LARGE: [[u8; 10]; 16] = [
+    [
+        0b00111100, //   ####
+        0b01111110, //  ######
+        0b11100111, // ###  ###
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11100111, // ###  ###
+        0b01111110, //  ######
+        0b00111100, //   ####
+    ],
+    [
+        0b00011000, //    ##
+        0b00111000, //   ###
+        0b01011000, //  # ##
+        0b00011000, //    ##
+        0b00011000, //    ##
+        0b00011000, //    ##
+        0b00011000, //    ##
+        0b00011000, //    ##
+        0b00011000, //    ##
+        0b00111100, //   ####
+    ],
+    [
+        0b00111110, //   #####
+        0b01111111, //  #######
+        0b11000011, // ##    ##
+        0b00000110, //      ##
+        0b00001100, //     ##
+        0b00011000, //    ##
+        0b00110000, //   ##
+        0b01100000, //  ##
+        0b11111111, // ########
+        0b11111111, // ########
+    ],
+    [
+        0b00111100, //   ####
+        0b01111110, //  ######
+        0b11000011, // ##    ##
+        0b00000011, //       ##
+        0b00001110, //     ###
+        0b00001110, //     ###
+        0b00000011, //       ##
+        0b11000011, // ##    ##
+        0b01111110, //  ######
+        0b00111100, //   ####
+    ],
+    [
+        0b00000110, //      ##
+        0b00001110, //     ###
+        0b00011110, //    ####
+        0b00110110, //   ## ##
+        0b01100110, //  ##  ##
+        0b11000110, // ##   ##
+        0b11111111, // ########
+        0b11111111, // ########
+        0b00000110, //      ##
+        0b00000110, //      ##
+    ],
+    [
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11111100, // ######
+        0b11111110, // #######
+        0b00000011, //       ##
+        0b11000011, // ##    ##
+        0b01111110, //  ######
+        0b00111100, //   ####
+    ],
+    [
+        0b00111110, //   #####
+        0b01111100, //  #####
+        0b11100000, // ###
+        0b11000000, // ##
+        0b11111100, // ######
+        0b11111110, // #######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b01111110, //  ######
+        0b00111100, //   ####
+    ],
+    [
+        0b11111111, // ########
+        0b11111111, // ########
+        0b00000011, //       ##
+        0b00000110, //      ##
+        0b00001100, //     ##
+        0b00011000, //    ##
+        0b00110000, //   ##
+        0b01100000, //  ##
+        0b01100000, //  ##
+        0b01100000, //  ##
+    ],
+    [
+        0b00111100, //   ####
+        0b01111110, //  ######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b01111110, //  ######
+        0b01111110, //  ######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b01111110, //  ######
+        0b00111100, //   ####
+    ],
+    [
+        0b00111100, //   ####
+        0b01111110, //  ######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b01111111, //  #######
+        0b00111111, //   ######
+        0b00000011, //       ##
+        0b00000011, //       ##
+        0b00111110, //   #####
+        0b01111100, //  #####
+    ],
+    [
+        0b01111110, //  ######
+        0b11111111, // ########
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+    ],
+    [
+        0b11111100, // ######
+        0b11111100, // ######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11111100, // ######
+        0b11111100, // ######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11111100, // ######
+        0b11111100, // ######
+    ],
+    [
+        0b00111100, //   ####
+        0b11111111, // ########
+        0b11000011, // ##    ##
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11000011, // ##    ##
+        0b11111111, // ########
+        0b00111100, //   ####
+    ],
+    [
+        0b11111100, // ######
+        0b11111110, // #######
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11000011, // ##    ##
+        0b11111110, // #######
+        0b11111100, // ######
+    ],
+    [
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11111111, // ########
+        0b11111111, // ########
+    ],
+    [
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11111111, // ########
+        0b11111111, // ########
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11000000, // ##
+        0b11000000, // ##
+    ],
+];
+
 /// Memory available to CHIP-8.
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Memory {
     /// RAM.
     ///
     /// * `0x000..=0x1FFF` is unused (except the font).
     /// * Font is stored in `0x50..=0x9F` by convention.
+    /// * Large font is stored in `0xA0..=0x13F`.
     /// * Programs are stored in `0x200..`.
-    pub ram: [u8; Self::SIZE_RAM],
-    /// Display buffer containing the state of each pixel.
     ///
-    /// Stored in `[y][x]` format.
-    pub vram: [[bool; Self::SIZE_DISPLAY_WIDTH]; Self::SIZE_DISPLAY_HEIGHT],
+    /// [`Memory::SIZE_RAM`] bytes long unless another size was chosen with [`crate::Chip8::with_ram_size`].
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_ram"))]
+    pub ram: Vec<u8>,
+    /// Display buffer containing the state of each pixel.
+    pub vram: Vram,
     /// Indexes in RAM of current subroutines.
     pub stack: Vec<u16>,
     /// Index in RAM where current execution is.
@@ -138,31 +343,80 @@ pub struct Memory {
     /// Should decrement at 60Hz rate.
     pub st: u8,
     /// Index register often used to store memory addresses.
+    ///
+    /// Instructions reading or writing RAM through it wrap around to the start of RAM
+    /// instead of going past the end, the same as the program counter.
     pub i: u16,
     /// General purpose registers.
     pub v: [u8; Self::SIZE_REGISTERS],
     /// If the keys are pressed.
     pub keys: [bool; Self::SIZE_KEYS],
+    /// 1-bit samples played while the sound timer is running, from the most significant bit of the first byte.
+    pub audio_pattern: [u8; Self::SIZE_AUDIO_PATTERN],
+    /// Playback rate of the audio pattern, `4000 * 2 ^ ((pitch - 64) / 48)` samples per second.
+    pub pitch: u8,
+    /// Addresses in RAM written by instructions during the last step or frame, without duplicates.
+    ///
+    /// Debugging aid, so it is ignored by serialization.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub written: Vec<u16>,
 }
 
 impl Memory {
-    pub const SIZE_RAM: usize = 4 * 1024;
+    /// Default RAM size.
+    /// 4 KB like the original hardware, or 64 KB with the `xo-chip` feature so `F000 NNNN` can address all of it.
+    pub const SIZE_RAM: usize = if cfg!(feature = "xo-chip") {
+        64 * 1024
+    } else {
+        4 * 1024
+    };
+    /// Largest RAM size, every byte of it can be reached with a 16-bit address.
+    pub const SIZE_RAM_MAX: usize = 64 * 1024;
     pub const SIZE_REGISTERS: usize = 16;
     pub const SIZE_KEYS: usize = 16;
+    /// How many nested subroutine calls the original hardware supported.
+    pub const SIZE_STACK: usize = 16;
+    pub const SIZE_AUDIO_PATTERN: usize = 16;
     pub const SIZE_DISPLAY_WIDTH: usize = 64;
     pub const SIZE_DISPLAY_HEIGHT: usize = 32;
 
     pub const INDEX_PROGRAM_START: u16 = 0x200;
 
     pub const INDEX_FONT_START: usize = 0x50;
+    /// Large font is stored right after the small one.
+    pub const INDEX_FONT_LARGE_START: usize = Self::INDEX_FONT_START + 16 * 5;
+    /// Register that instructions store their flag in, like carry, borrow, or sprite collision.
+    ///
+    /// Instructions write the flag after the result, so the flag wins when the result is stored in `VF` too.
     pub const INDEX_FLAG_REGISTER: usize = Self::SIZE_REGISTERS - 1;
+
+    /// Pitch that plays the audio pattern at 4000 samples per second.
+    pub const PITCH_DEFAULT: u8 = 64;
+}
+
+impl Memory {
+    /// Built-in font, 4x5 pixels per character.
+    pub const FONT: [[u8; 5]; 16] = FONT;
+    /// Built-in large font, 8x10 pixels per character.
+    pub const FONT_LARGE: [[u8; 10]; 16] = FONT_LARGE;
 }
 
 impl Default for Memory {
     fn default() -> Self {
+        Self::with_ram_size(Self::SIZE_RAM)
+    }
+}
+
+impl Memory {
+    /// Empty memory with the font loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Length of RAM, must pass [`Memory::is_valid_ram_size`].
+    pub(crate) fn with_ram_size(size: usize) -> Self {
         let mut s = Self {
-            ram: [0; Self::SIZE_RAM],
-            vram: [[false; Self::SIZE_DISPLAY_WIDTH]; Self::SIZE_DISPLAY_HEIGHT],
+            ram: vec![0; size],
+            vram: Vram::new(),
             stack: Vec::default(),
             pc: Self::INDEX_PROGRAM_START,
             dt: 0,
@@ -170,28 +424,139 @@ impl Default for Memory {
             i: 0,
             v: [0; Self::SIZE_REGISTERS],
             keys: [false; Self::SIZE_KEYS],
+            audio_pattern: [0; Self::SIZE_AUDIO_PATTERN],
+            pitch: Self::PITCH_DEFAULT,
+            written: Vec::default(),
         };
         s.clear_memory();
         s
     }
+
+    /// Whether RAM of a size fits the font, at least one instruction of a program, and can be fully addressed.
+    pub fn is_valid_ram_size(size: usize) -> bool {
+        (Self::INDEX_PROGRAM_START as usize + 2..=Self::SIZE_RAM_MAX).contains(&size)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_ram<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    let ram = Vec::<u8>::deserialize(deserializer)?;
+    if Memory::is_valid_ram_size(ram.len()) {
+        Ok(ram)
+    } else {
+        Err(D::Error::invalid_length(ram.len(), &"a valid RAM size"))
+    }
 }
 
 impl Memory {
-    /// Reset memory and load a ROM into RAM.
+    /// Reset memory and load a ROM into RAM, pointing the program counter at its start.
     ///
     /// # Arguments
     ///
     /// * `program` - Program to load.
-    pub(crate) fn load(&mut self, rom: &[u8]) {
+    /// * `start` - Address of the first byte of the program.
+    pub(crate) fn load(&mut self, rom: &[u8], start: u16) {
         self.clear_memory();
-        self.ram[Self::INDEX_PROGRAM_START as usize..][..rom.len()].copy_from_slice(rom);
+        self.ram[start as usize..][..rom.len()].copy_from_slice(rom);
+        self.pc = start;
+    }
+
+    /// Replace the font used by `Fx29`.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - 5 rows for each of the 16 characters.
+    pub(crate) fn set_font(&mut self, font: &[[u8; 5]; 16]) {
+        self.ram[Self::INDEX_FONT_START..][..16 * 5].copy_from_slice(font.flatten());
+    }
+
+    /// Replace the large font.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - 10 rows for each of the 16 characters.
+    pub(crate) fn set_large_font(&mut self, font: &[[u8; 10]; 16]) {
+        self.ram[Self::INDEX_FONT_LARGE_START..][..16 * 10].copy_from_slice(font.flatten());
     }
 
     /// Advance program counter to the next instruction.
+    /// Wraps around to the start of RAM after reaching the end, like the original hardware.
     ///
     /// **NOTE:** Does not execute any instructions.
     pub(crate) fn increment_pc(&mut self) {
-        self.pc += 2;
+        self.set_pc(self.pc.wrapping_add(2));
+    }
+
+    /// Advance program counter past the next instruction, including the operand of `F000 NNNN`.
+    ///
+    /// **NOTE:** Does not execute any instructions.
+    pub(crate) fn skip_instruction(&mut self) {
+        let long = Opcode::from(self.word(self.pc)).has_operand();
+        self.increment_pc();
+        if long {
+            self.increment_pc();
+        }
+    }
+
+    /// Move program counter to an address.
+    /// Addresses past the end of RAM wrap around to the start.
+    pub(crate) fn set_pc(&mut self, address: u16) {
+        self.pc = Addr(address).masked(self.ram.len()) as u16;
+    }
+
+    /// Read 2 bytes of RAM as a big-endian word.
+    /// Addresses past the end of RAM wrap around to the start.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Address of the first byte.
+    pub(crate) fn word(&self, address: u16) -> u16 {
+        let address = Addr(address).masked(self.ram.len());
+        u16::from_be_bytes([self.ram[address], self.ram[(address + 1) % self.ram.len()]])
+    }
+
+    /// Index in RAM of a byte relative to the index register.
+    /// Addresses past the end of RAM wrap around to the start.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Distance from the address in the index register.
+    pub(crate) fn i_address(&self, offset: usize) -> usize {
+        (Addr(self.i).masked(self.ram.len()) + offset) % self.ram.len()
+    }
+
+    /// Write a byte of RAM on behalf of an instruction, remembering the address in [`Memory::written`].
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Index in RAM, must be less than its length.
+    /// * `value` - Byte to write.
+    pub(crate) fn write(&mut self, address: usize, value: u8) {
+        self.ram[address] = value;
+        let address = address as u16;
+        if !self.written.contains(&address) {
+            self.written.push(address);
+        }
+    }
+
+    /// Flip the pixels of a sprite on the display, like `Dxyn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the left edge of the sprite, wraps around the display.
+    /// * `y` - Row of the top edge of the sprite, wraps around the display.
+    /// * `sprite` - Rows of the sprite, 8 pixels each. Rows and columns past the edges are clipped.
+    ///
+    /// # Returns
+    ///
+    /// Whether any pixel was turned off, the value `Dxyn` stores in `VF`.
+    pub(crate) fn draw_sprite(&mut self, x: usize, y: usize, sprite: &[u8]) -> bool {
+        self.vram.draw_sprite(x, y, sprite)
     }
 
     /// Perform an update of the timer.
@@ -205,18 +570,12 @@ impl Memory {
         }
     }
 
-    /// Reset display memory.
-    pub(crate) fn clear_vram(&mut self) {
-        self.vram
-            .iter_mut()
-            .for_each(|e| e.iter_mut().for_each(|e| *e = false));
-    }
-
     /// Reset all memory and load font into RAM.
     fn clear_memory(&mut self) {
         self.ram.iter_mut().for_each(|e| *e = 0);
-        self.ram[Memory::INDEX_FONT_START..][..16 * 5].copy_from_slice(FONT.flatten());
-        self.clear_vram();
+        self.set_font(&FONT);
+        self.set_large_font(&FONT_LARGE);
+        self.vram.clear();
         self.stack.clear();
         self.v.iter_mut().for_each(|e| *e = 0);
         self.pc = Self::INDEX_PROGRAM_START;
@@ -224,6 +583,9 @@ impl Memory {
         self.st = 0;
         self.i = 0;
         self.keys = [false; Self::SIZE_KEYS];
+        self.audio_pattern = [0; Self::SIZE_AUDIO_PATTERN];
+        self.pitch = Self::PITCH_DEFAULT;
+        self.written.clear();
     }
 }
 
@@ -243,7 +605,7 @@ mod tests {
             0x61, 0x02, // Load 2 into register 1
             0x71, 0x03, // Add 3 to it
         ]);
-        memory.vram[0].iter_mut().for_each(|e| *e = true);
+        memory.vram[0] = Vram::FULL_ROW;
         memory.stack.push(Memory::INDEX_PROGRAM_START);
         memory.dt = 60;
         memory.st = 10;
@@ -276,8 +638,12 @@ mod tests {
             FONT.flatten()
         );
         assert_eq!(
-            target.ram[0xA0..Memory::SIZE_RAM],
-            [0; Memory::SIZE_RAM - 0xA0]
+            &target.ram[Memory::INDEX_FONT_LARGE_START..][..16 * 10],
+            FONT_LARGE.flatten()
+        );
+        assert_eq!(
+            target.ram[0x140..Memory::SIZE_RAM],
+            [0; Memory::SIZE_RAM - 0x140]
         );
         Ok(())
     }
@@ -286,10 +652,7 @@ mod tests {
     fn default_initializes_display() -> Result<()> {
         let target = Memory::default();
 
-        assert_eq!(
-            target.vram,
-            [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT]
-        );
+        assert_eq!(target.vram, Vram::new());
         Ok(())
     }
 
@@ -320,12 +683,34 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn set_font_replaces_font(mut target: Memory, mut result: Memory) -> Result<()> {
+        let font = [[0xAB; 5]; 16];
+        target.set_font(&font);
+
+        result.ram[0x50..0xA0].fill(0xAB);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_large_font_replaces_large_font(mut target: Memory, mut result: Memory) -> Result<()> {
+        let font = [[0xCD; 10]; 16];
+        target.set_large_font(&font);
+
+        result.ram[0xA0..0x140].fill(0xCD);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn load_loads() -> Result<()> {
         let mut target = Memory::default();
         let mut result = Memory::default();
 
-        target.load(&[10, 20, 30]);
+        target.load(&[10, 20, 30], Memory::INDEX_PROGRAM_START);
 
         result.ram[Memory::INDEX_PROGRAM_START as usize..][..3].copy_from_slice(&[10, 20, 30]);
 
@@ -333,12 +718,26 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn load_loads_at_start() -> Result<()> {
+        let mut target = Memory::default();
+        let mut result = Memory::default();
+
+        target.load(&[10, 20, 30], 0x600);
+
+        result.ram[0x600..][..3].copy_from_slice(&[10, 20, 30]);
+        result.pc = 0x600;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn load_resets_memory(
         mut target: Memory,
         #[with(Memory::default())] mut result: Memory,
     ) -> Result<()> {
-        target.load(&[]);
+        target.load(&[], Memory::INDEX_PROGRAM_START);
 
         assert_eq!(target, result);
         Ok(())
@@ -356,6 +755,41 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn increment_pc_wraps(
+        mut target: Memory,
+        mut result: Memory,
+        #[values((0xFFE, 0x000), (0xFFF, 0x001))] pc: (u16, u16),
+    ) -> Result<()> {
+        let (pc, next) = pc;
+        target.pc = pc;
+        target.increment_pc();
+
+        result.pc = next;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn high_addresses_wrap_into_ram() -> Result<()> {
+        let mut target = Memory::with_ram_size(0x300);
+        target.ram[0x2FF] = 0xAB;
+        target.ram[0x000] = 0xCD;
+        target.i = 0xFFFF;
+
+        assert_eq!(target.word(0x2FF), 0xABCD);
+        assert_eq!(target.word(0x5FF), 0xABCD);
+        assert_eq!(target.i_address(0), 0xFFFF % 0x300);
+        assert_eq!(target.i_address(2), (0xFFFF + 2) % 0x300);
+
+        target.set_pc(0xFFFF);
+
+        assert_eq!(target.pc, 0xFFFF % 0x300);
+        Ok(())
+    }
+
     #[rstest]
     fn advance_timer_decrements(mut target: Memory, mut result: Memory) -> Result<()> {
         for _ in 0..3 {
@@ -370,23 +804,58 @@ mod tests {
     }
 
     #[rstest]
-    fn advance_timer_doesnt_underflow_if_0(mut target: Memory, mut result: Memory) -> Result<()> {
-        for _ in 0..100 {
-            target.advance_timer();
-        }
+    fn draw_sprite_reports_collision(mut target: Memory, mut result: Memory) -> Result<()> {
+        // Row 0 is full, row 1 is empty
+        let collision = target.draw_sprite(8, 0, &[0b10000001, 0b11000000]);
 
-        result.dt = 0;
-        result.st = 0;
+        result.vram[0] = Vram::FULL_ROW & !(Vram::pixel_mask(8) | Vram::pixel_mask(15));
+        result.vram[1] = Vram::pixel_mask(8) | Vram::pixel_mask(9);
 
+        assert!(collision);
         assert_eq!(target, result);
+
+        let collision = target.draw_sprite(8, 1, &[0b00100000]);
+
+        assert!(!collision);
+        Ok(())
+    }
+
+    #[rstest]
+    fn draw_sprite_clips_at_edges(mut target: Memory) -> Result<()> {
+        target.vram.clear();
+
+        let collision =
+            target.draw_sprite(Vram::WIDTH - 2, Vram::HEIGHT - 1, &[0b11110000, 0b11110000]);
+
+        assert!(!collision);
+        assert_eq!(
+            target.vram.lit_pixels().collect::<Vec<_>>(),
+            vec![
+                (Vram::WIDTH - 2, Vram::HEIGHT - 1),
+                (Vram::WIDTH - 1, Vram::HEIGHT - 1)
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn draw_sprite_wraps_start(mut target: Memory) -> Result<()> {
+        target.vram.clear();
+
+        target.draw_sprite(Vram::WIDTH + 1, Vram::HEIGHT + 2, &[0b10000000]);
+
+        assert_eq!(target.vram.lit_pixels().collect::<Vec<_>>(), vec![(1, 2)]);
         Ok(())
     }
 
     #[rstest]
-    fn clear_vram_resets(mut target: Memory, mut result: Memory) -> Result<()> {
-        target.clear_vram();
+    fn advance_timer_doesnt_underflow_if_0(mut target: Memory, mut result: Memory) -> Result<()> {
+        for _ in 0..100 {
+            target.advance_timer();
+        }
 
-        result.vram = [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+        result.dt = 0;
+        result.st = 0;
 
         assert_eq!(target, result);
         Ok(())
@@ -1,3 +1,9 @@
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use core::{iter, mem};
+
+#[cfg(feature = "std")]
 use thiserror::Error;
 
 use crate::instruction::*;
@@ -5,41 +11,159 @@ use crate::instruction::*;
 use super::*;
 
 /// Combines [`ParseError`] and [`ExecuteError`]
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
 pub enum InstructionError {
-    #[error("parse error {0}")]
-    ParseError(ParseError),
-    #[error("execute error {0}")]
+    /// Opcode in RAM could not be parsed.
+    #[cfg_attr(feature = "std", error("parse error {error} at {address:#05X}"))]
+    Parse {
+        error: ParseError,
+        /// Where the opcode is stored.
+        address: u16,
+    },
+    #[cfg_attr(feature = "std", error("execute error {0}"))]
     ExecuteError(ExecuteError),
 }
 
-impl From<ParseError> for InstructionError {
-    fn from(value: ParseError) -> Self {
-        Self::ParseError(value)
-    }
-}
-
 impl From<ExecuteError> for InstructionError {
     fn from(value: ExecuteError) -> Self {
         Self::ExecuteError(value)
     }
 }
 
+/// Errors encountered while placing data into RAM.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
+pub enum LoadError {
+    #[cfg_attr(
+        feature = "std",
+        error("{len} bytes at {address:#05X} do not fit into RAM")
+    )]
+    OutOfBounds { address: u16, len: usize },
+    #[cfg_attr(
+        feature = "std",
+        error("RAM of {0} bytes does not fit a program or cannot be addressed")
+    )]
+    InvalidRamSize(usize),
+    #[cfg_attr(
+        feature = "std",
+        error("program start {0:#05X} is inside the interpreter area before 0x200")
+    )]
+    InvalidProgramStart(u16),
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum State {
     #[default]
     Ready,
     WaitingForKey {
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_register"))]
         vx: usize,
     },
+    WaitingForVblank,
+    /// Stopped by `00FD` until a ROM is loaded.
+    Halted,
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_register<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    let vx = usize::deserialize(deserializer)?;
+    if vx < Memory::SIZE_REGISTERS {
+        Ok(vx)
+    } else {
+        Err(D::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(vx as u64),
+            &"a register in 0-F range",
+        ))
+    }
+}
+
+/// Drawing activity during a single timer tick.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawStats {
+    /// How many times `Dxyn` was executed.
+    pub sprites: usize,
+    /// How many of the drawn sprites turned off a pixel and set `VF` to 1.
+    pub collisions: usize,
+}
+
+/// Result of a single fetch decode execute cycle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StepOutcome {
+    /// The instruction was executed.
+    Executed,
+    /// Nothing was executed because the system is waiting for a key press or a timer update.
+    Blocked,
+    /// The instruction was executed and changed a watched location, see [`Chip8::add_watch`].
+    WatchHit(Watch),
+    /// The instruction was executed and moved the program counter to a breakpoint, see [`Chip8::add_breakpoint`].
+    BreakpointHit(u16),
 }
 
+impl StepOutcome {
+    /// Whether the execution stopped for a watch or a breakpoint.
+    pub fn is_hit(self) -> bool {
+        matches!(
+            self,
+            StepOutcome::WatchHit(_) | StepOutcome::BreakpointHit(_)
+        )
+    }
+}
+
+/// Reason [`Chip8::run_until_key_wait`] stopped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RunOutcome {
+    /// `Fx0A` is waiting for a key press.
+    WaitingForKey,
+    /// An instruction changed a watched location, see [`Chip8::add_watch`].
+    WatchHit(Watch),
+    /// An instruction moved the program counter to a breakpoint, see [`Chip8::add_breakpoint`].
+    BreakpointHit(u16),
+    /// The step limit was reached without the program waiting for a key, like in a `JP` to itself loop.
+    Stalled,
+}
+
+/// Saved state of the machine, see [`Chip8::checkpoint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint(Box<Chip8>);
+
 /// Main structure used to emulate CHIP-8.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chip8 {
     pub(crate) config: Config,
     pub(crate) memory: Memory,
     pub(crate) state: State,
+    /// Timer updates since `Fx0A` started waiting, see [`Config::wait_key_timeout_frames`].
+    pub(crate) wait_key_frames: u32,
+    /// Drawing activity during the last complete timer tick.
+    pub(crate) draw_stats: DrawStats,
+    /// Drawing activity since the last timer tick.
+    pub(crate) draw_stats_pending: DrawStats,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) rng: Rng,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) events: Events,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) watches: Watches,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) breakpoints: Breakpoints,
+    /// Keys that would have satisfied `Fx0A` since the last timer update, from the oldest to the newest.
+    /// Lets a tap that comes and goes before `Fx0A` executes in the same frame still be stored.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) key_buffer: VecDeque<u8>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) history: History,
+    /// Whether timer updates leave the delay and sound timers as they are, see [`Chip8::set_timers_frozen`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) timers_frozen: bool,
 }
 
 impl Default for Chip8 {
@@ -48,18 +172,135 @@ impl Default for Chip8 {
     }
 }
 
+/// Create a machine with the default configuration and a ROM loaded, see [`Chip8::from_rom`].
+impl TryFrom<&[u8]> for Chip8 {
+    type Error = LoadError;
+
+    fn try_from(rom: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_rom(rom, Config::default())
+    }
+}
+
 impl Chip8 {
     /// How many times per second should the timer update.
     pub const FREQUENCY_TIMER_UPDATE: usize = 60;
+    /// How many instructions per second most programs are written for, around what later interpreters ran at.
+    ///
+    /// Run [`Chip8::instructions_per_frame`] of it between each timer update.
+    pub const DEFAULT_INSTRUCTIONS_PER_SECOND: usize = 700;
+    /// How many machine cycles the COSMAC VIP ran each 60 hz frame, running at 1.76 megahertz with 8 clocks per cycle.
+    pub const CYCLES_PER_FRAME: u32 = 1_760_640 / 8 / Self::FREQUENCY_TIMER_UPDATE as u32;
+    /// How many keys are remembered for `Fx0A` between timer updates.
+    const KEY_BUFFER_CAPACITY: usize = Memory::SIZE_KEYS;
+    /// Default amount of instructions kept by [`Chip8::with_history`].
+    pub const HISTORY_CAPACITY: usize = 256;
 }
 
 impl Chip8 {
     pub fn new(config: Config) -> Self {
         Self {
+            memory: Memory {
+                pc: config.program_start,
+                ..Memory::default()
+            },
             config,
-            memory: Memory::default(),
             state: State::default(),
+            wait_key_frames: 0,
+            draw_stats: DrawStats::default(),
+            draw_stats_pending: DrawStats::default(),
+            rng: Rng::default(),
+            events: Events::default(),
+            watches: Watches::default(),
+            breakpoints: Breakpoints::default(),
+            key_buffer: VecDeque::new(),
+            history: History::default(),
+            timers_frozen: false,
+        }
+    }
+
+    /// How many instructions to run between timer updates to keep a rate,
+    /// rounded to the nearest whole amount, but at least 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions_per_second` - Rate to keep, usually [`Chip8::DEFAULT_INSTRUCTIONS_PER_SECOND`].
+    pub const fn instructions_per_frame(instructions_per_second: usize) -> usize {
+        let frames = Self::FREQUENCY_TIMER_UPDATE;
+        let instructions = (instructions_per_second + frames / 2) / frames;
+        if instructions == 0 {
+            1
+        } else {
+            instructions
+        }
+    }
+
+    /// Configure a machine with a ROM, font, seed, and debugging aids in one place.
+    pub fn builder<'a>() -> Chip8Builder<'a> {
+        Chip8Builder::default()
+    }
+
+    /// Start recording the most recently executed instructions, see [`Chip8::history`].
+    ///
+    /// Recording is off by default to keep stepping cheap.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - How many instructions to keep, usually [`Chip8::HISTORY_CAPACITY`]. 0 turns recording off.
+    #[must_use]
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.history = History::new(capacity);
+        self
+    }
+
+    /// Replace RAM with one of a different size, for XO-CHIP programs that need 64 KB or for smaller test setups.
+    ///
+    /// Resets memory, so call this before loading a ROM.
+    /// The absolute addresses like [`Memory::INDEX_PROGRAM_START`] stay the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Length of RAM in bytes, from just past [`Memory::INDEX_PROGRAM_START`] up to [`Memory::SIZE_RAM_MAX`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if the size is outside of that range.
+    pub fn with_ram_size(mut self, size: usize) -> Result<Self, LoadError> {
+        if !Memory::is_valid_ram_size(size) {
+            return Err(LoadError::InvalidRamSize(size));
         }
+        self.memory = Memory::with_ram_size(size);
+        self.memory.pc = self.config.program_start;
+
+        Ok(self)
+    }
+
+    /// Emulation quirks the machine runs with.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Address where ROMs are loaded and execution starts, see [`Config::program_start`].
+    pub fn program_start(&self) -> u16 {
+        self.config.program_start
+    }
+
+    /// Length of RAM in bytes, [`Memory::SIZE_RAM`] unless changed with [`Chip8::with_ram_size`].
+    pub fn ram_size(&self) -> usize {
+        self.memory.ram.len()
+    }
+
+    /// Most recently executed instructions with their addresses, from the oldest to the newest.
+    ///
+    /// Empty unless recording was turned on with [`Chip8::with_history`].
+    /// Includes an instruction that failed to execute, so it can be inspected after an error.
+    pub fn history(&self) -> &VecDeque<(u16, Instruction)> {
+        self.history.entries()
+    }
+
+    /// Send notable events to a channel, or stop sending them with [`None`].
+    #[cfg(feature = "std")]
+    pub fn set_events(&mut self, sender: Option<std::sync::mpsc::Sender<Chip8Event>>) {
+        self.events.sender = sender;
     }
 
     /// Access system memory.
@@ -67,191 +308,2196 @@ impl Chip8 {
         &self.memory
     }
 
-    /// Reset memory and load a ROM into RAM.
+    /// State of each pixel of the display.
+    ///
+    /// Stored in `[y][x]` format.
+    pub fn frame_buffer(
+        &self,
+    ) -> [[bool; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT] {
+        self.memory.vram.frame_buffer()
+    }
+
+    /// Read a single pixel of the display.
     ///
     /// # Arguments
     ///
-    /// * `program` - Program to load.
-    pub fn load(&mut self, rom: &[u8]) {
-        self.memory.load(rom);
+    /// * `x` - Column of the pixel, returns [`None`] if it is outside of the display.
+    /// * `y` - Row of the pixel, returns [`None`] if it is outside of the display.
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<bool> {
+        (x < Memory::SIZE_DISPLAY_WIDTH && y < Memory::SIZE_DISPLAY_HEIGHT)
+            .then(|| self.memory.vram.pixel(x, y))
     }
 
-    /// Perform a fetch decode execute cycle.
-    /// Should be called at around 500-1000hz.
+    /// Turn a single pixel of the display on or off.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Column of the pixel.
+    /// * `y` - Row of the pixel.
+    /// * `on` - Whether the pixel should be lit.
     ///
     /// # Errors
     ///
-    /// Return an [`InstructionError`] if the instruction did not execute correctly.
-    pub fn advance_instruction(&mut self) -> Result<(), InstructionError> {
-        if self.state == State::Ready && self.memory.dt == 0 {
-            let opcode = Opcode::from((
-                self.memory.ram[self.memory.pc as usize],
-                self.memory.ram[self.memory.pc as usize + 1],
-            ));
-            self.memory.increment_pc();
-            self.execute(&Instruction::try_from(opcode)?)?;
+    /// Returns an [`InstructionError`] if the pixel is outside of the display.
+    pub fn set_pixel(&mut self, x: usize, y: usize, on: bool) -> Result<(), InstructionError> {
+        if x >= Memory::SIZE_DISPLAY_WIDTH || y >= Memory::SIZE_DISPLAY_HEIGHT {
+            return Err(ExecuteError::InvalidPixel(x, y).into());
         }
+        self.memory.vram.set_pixel(x, y, on);
 
         Ok(())
     }
 
-    /// Perform an update of the timer.
+    /// Borrow the display memory without copying it.
+    pub fn vram(&self) -> &Vram {
+        &self.memory.vram
+    }
+
+    /// Hash of the whole RAM, to tell if a program reached an expected state without comparing every byte.
     ///
-    /// Should be called at a fixed rate of 60 hz.
-    /// The constant is [`Chip8::FREQUENCY_TIMER_UPDATE`]
-    pub fn advance_timer(&mut self) {
-        self.memory.advance_timer();
+    /// Equal RAM always gives equal checksums, different RAM almost always gives different ones.
+    pub fn ram_checksum(&self) -> u64 {
+        checksum::fnv1a(self.memory.ram.iter().copied())
     }
 
-    /// Presses a key by the index.
+    /// Hash of the display, like [`Chip8::ram_checksum`].
+    pub fn display_checksum(&self) -> u64 {
+        checksum::fnv1a(
+            self.memory
+                .vram
+                .rows()
+                .iter()
+                .flat_map(|row| row.to_be_bytes()),
+        )
+    }
+
+    /// Drawing activity during the last complete timer tick.
+    ///
+    /// Multiply by [`Chip8::FREQUENCY_TIMER_UPDATE`] to get the rate per second.
+    pub fn draw_stats(&self) -> DrawStats {
+        self.draw_stats
+    }
+
+    /// Whether the sound timer is running and the buzzer should be audible.
+    pub fn is_beeping(&self) -> bool {
+        self.memory.st > 0
+    }
+
+    /// 1-bit samples to play in a loop while [`Chip8::is_beeping`], loaded by XO-CHIP `F002`.
+    ///
+    /// Starts with the most significant bit of the first byte.
+    pub fn audio_pattern(&self) -> &[u8; Memory::SIZE_AUDIO_PATTERN] {
+        &self.memory.audio_pattern
+    }
+
+    /// Playback rate of [`Chip8::audio_pattern`], set by XO-CHIP `Fx3A`.
+    ///
+    /// The pattern plays at `4000 * 2 ^ ((pitch - 64) / 48)` samples per second.
+    pub fn pitch(&self) -> u8 {
+        self.memory.pitch
+    }
+
+    /// Create a machine with a ROM already loaded.
     ///
     /// # Arguments
     ///
-    /// * `key` - The index of the key. Must be between 0x0 and 0xF (inclusive).
+    /// * `rom` - Program to load.
+    /// * `config` - Emulation quirks.
     ///
     /// # Errors
     ///
-    /// Returns an [`InstructionError`] if the provided key is outside the valid range.
-    pub fn press_key(&mut self, key: u8) -> Result<(), InstructionError> {
-        if key > 0xF {
-            return Err(ExecuteError::InvalidKey(key).into());
-        }
-
-        self.memory.keys[key as usize] = true;
-
-        Ok(())
+    /// Returns a [`LoadError`] if the ROM can not be loaded, see [`Chip8::try_load`].
+    pub fn from_rom(rom: &[u8], config: Config) -> Result<Self, LoadError> {
+        let mut chip = Self::new(config);
+        chip.try_load(rom)?;
+        Ok(chip)
     }
 
-    /// Unpress a key by the index.
-    /// Also unblocks the execution if the system was waiting for a key press.
+    /// Create a machine in a prepared state, ready to execute the instruction at the program counter.
+    /// Useful to set up tests without running a program to get there.
     ///
     /// # Arguments
     ///
-    /// * `key` - The index of the key. Must be between 0x0 and 0xF (inclusive).
+    /// * `config` - Emulation quirks.
+    /// * `memory` - Registers, RAM, display, stack, and timers to start with.
+    ///   Usually [`Memory::default`] with the parts that matter changed.
     ///
     /// # Errors
     ///
-    /// Returns an [`InstructionError`] if the provided key is outside the valid range.
-    pub fn unpress_key(&mut self, key: u8) -> Result<(), InstructionError> {
-        if key > 0xF {
-            return Err(ExecuteError::InvalidKey(key).into());
+    /// Returns a [`LoadError`] if the RAM size does not pass [`Memory::is_valid_ram_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chip_8::{Chip8, Config, Memory};
+    ///
+    /// let mut memory = Memory::default();
+    /// memory.v[0x0] = 0xFF;
+    /// memory.v[0x1] = 0x02;
+    /// // Add register 1 to register 0.
+    /// memory.ram[0x200..0x202].copy_from_slice(&[0x80, 0x14]);
+    ///
+    /// let mut chip = Chip8::with_state(Config::default(), memory)?;
+    /// chip.advance_instruction()?;
+    ///
+    /// assert_eq!(chip.register(0x0), Some(0x01));
+    /// assert_eq!(chip.register(0xF), Some(0x01));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_state(config: Config, mut memory: Memory) -> Result<Self, LoadError> {
+        if !Memory::is_valid_ram_size(memory.ram.len()) {
+            return Err(LoadError::InvalidRamSize(memory.ram.len()));
         }
+        memory.written.clear();
 
-        self.memory.keys[key as usize] = false;
+        Ok(Self {
+            memory,
+            ..Self::new(config)
+        })
+    }
 
-        if let State::WaitingForKey { vx } = self.state {
-            self.memory.v[vx] = key;
-            self.state = State::Ready;
+    /// Reset memory and load a ROM into RAM, if it fits.
+    ///
+    /// # Arguments
+    ///
+    /// * `rom` - Program to load.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if [`Config::program_start`] is before [`Memory::INDEX_PROGRAM_START`],
+    /// where the font is, or the ROM does not fit into RAM after it, in which case nothing is changed.
+    pub fn try_load(&mut self, rom: &[u8]) -> Result<(), LoadError> {
+        let start = self.config.program_start;
+        if start < Memory::INDEX_PROGRAM_START {
+            return Err(LoadError::InvalidProgramStart(start));
+        }
+        if start as usize + rom.len() > self.ram_size() {
+            return Err(LoadError::OutOfBounds {
+                address: start,
+                len: rom.len(),
+            });
         }
 
+        self.load(rom);
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    use eyre::Result;
-    use rstest::*;
-    use similar_asserts::assert_eq;
-
-    #[fixture]
-    fn target(#[default(Config::default())] config: Config) -> Chip8 {
-        let mut chip = Chip8::new(config);
 
-        chip.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4].copy_from_slice(&[
-            0x61, 0x02, // Load 2 into register 1
-            0x71, 0x03, // Add 3 to it
-        ]);
-        chip.memory.vram[0].iter_mut().for_each(|e| *e = true);
-        chip.memory.stack.push(Memory::INDEX_PROGRAM_START);
-        chip.memory.dt = 0;
-        chip.memory.st = 10;
-        chip.memory.i = 100;
-        chip.memory.v = [0, 1, 2, 3, 4, 5, 31, 59, 0, 1, 2, 3, 4, 5, 30, 60];
-        chip.memory.keys = [
-            true, false, true, false, true, false, true,
-            false, // First 8 are pressed and unpressed
-            false, false, false, false, false, false, false, false, // Last 8 are not pressed
-        ];
+    /// Reset memory and load a ROM into RAM at [`Config::program_start`], where execution starts.
+    /// The machine stops waiting for a key or the timer, and is no longer halted.
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - Program to load.
+    pub fn load(&mut self, rom: &[u8]) {
+        self.memory.load(rom, self.config.program_start);
+        self.state = State::default();
+    }
 
-        chip
+    /// Replace the font that `Fx29` points to.
+    ///
+    /// [`Chip8::load`] brings back the built-in [`Memory::FONT`], so call this after loading a ROM.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - 5 rows for each of the 16 characters.
+    pub fn set_font(&mut self, font: &[[u8; 5]; 16]) {
+        self.memory.set_font(font);
     }
 
-    #[fixture]
-    fn result(target: Chip8) -> Chip8 {
-        target.clone()
+    /// Replace the large font.
+    ///
+    /// [`Chip8::load`] brings back the built-in [`Memory::FONT_LARGE`], so call this after loading a ROM.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - 10 rows for each of the 16 characters.
+    pub fn set_large_font(&mut self, font: &[[u8; 10]; 16]) {
+        self.memory.set_large_font(font);
     }
 
-    #[rstest]
-    fn advance_instruction_ready(mut target: Chip8, mut result: Chip8) -> Result<()> {
-        target.advance_instruction()?;
-        target.advance_instruction()?;
+    /// Save the state of the machine, to go back to it later with [`Chip8::restore`].
+    ///
+    /// Cheaper than serializing the machine, but only lives in memory.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(Box::new(self.clone()))
+    }
 
-        result.memory.v[1] = 5;
-        result.memory.pc += 4;
+    /// Go back to a saved state of the machine.
+    ///
+    /// The random number generator, the events channel, the watches, the breakpoints, the buffered keys, the history,
+    /// and whether the timers are frozen are not part of the state
+    /// and are kept as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpoint` - State saved with [`Chip8::checkpoint`].
+    pub fn restore(&mut self, checkpoint: &Checkpoint) {
+        let Chip8 {
+            config,
+            memory,
+            state,
+            wait_key_frames,
+            draw_stats,
+            draw_stats_pending,
+            rng: _,
+            events: _,
+            watches: _,
+            breakpoints: _,
+            key_buffer: _,
+            history: _,
+            timers_frozen: _,
+        } = &*checkpoint.0;
 
-        assert_eq!(target, result);
-        Ok(())
+        self.config = config.clone();
+        self.memory = memory.clone();
+        self.state = *state;
+        self.wait_key_frames = *wait_key_frames;
+        self.draw_stats = *draw_stats;
+        self.draw_stats_pending = *draw_stats_pending;
     }
 
-    #[rstest]
-    fn advance_instruction_waiting_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
-        target.state = State::WaitingForKey { vx: 0x0 };
-        target.advance_instruction()?;
-        target.advance_instruction()?;
+    /// Copy bytes into RAM without resetting memory or moving the program counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the first byte.
+    /// * `bytes` - Data to copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if the data does not fit into RAM, in which case nothing is written.
+    pub fn load_at(&mut self, addr: u16, bytes: &[u8]) -> Result<(), LoadError> {
+        let out_of_bounds = LoadError::OutOfBounds {
+            address: addr,
+            len: bytes.len(),
+        };
 
-        result.state = State::WaitingForKey { vx: 0x0 };
+        self.memory
+            .ram
+            .get_mut(addr as usize..)
+            .and_then(|ram| ram.get_mut(..bytes.len()))
+            .ok_or(out_of_bounds)?
+            .copy_from_slice(bytes);
 
-        assert_eq!(target, result);
         Ok(())
     }
 
-    #[rstest]
-    fn advance_instruction_waiting_dt(mut target: Chip8, mut result: Chip8) -> Result<()> {
-        target.memory.dt = 10;
-        target.advance_instruction()?;
-        target.advance_instruction()?;
+    /// Perform a fetch decode execute cycle.
+    /// Should be called at around 500-1000hz.
+    ///
+    /// Execution that runs past the end of RAM wraps around to its start.
+    /// Every executed instruction is logged with [`log::trace!`], attach a logger to see them.
+    /// [`Memory::written`] is reset to the bytes the instruction writes.
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if the instruction did not execute correctly.
+    pub fn advance_instruction(&mut self) -> Result<StepOutcome, InstructionError> {
+        self.memory.written.clear();
+        self.step()
+    }
 
-        result.memory.dt = 10;
+    /// Fetch decode execute cycle that keeps adding to [`Memory::written`].
+    fn step(&mut self) -> Result<StepOutcome, InstructionError> {
+        if self.state != State::Ready || (self.memory.dt != 0 && !self.timers_frozen) {
+            return Ok(StepOutcome::Blocked);
+        }
 
-        assert_eq!(target, result);
-        Ok(())
-    }
+        let address = self.memory.pc;
+        let instruction = self.fetch();
+        self.memory.increment_pc();
+        let instruction =
+            instruction.map_err(|error| InstructionError::Parse { error, address })?;
+        if instruction.size() > 2 {
+            self.memory.increment_pc();
+        }
+        log::trace!("{address:#05X}: {instruction}");
+        self.history.push(address, instruction);
 
-    #[rstest]
-    fn advance_timer(mut target: Chip8, mut result: Chip8) -> Result<()> {
-        target.memory.dt = 10;
-        for _ in 0..3 {
-            target.advance_timer();
+        self.watches.latch(&self.memory);
+        self.execute(&instruction)?;
+        if let Some(watch) = self.watches.changed(&self.memory) {
+            log::trace!("{watch:?} changed");
+            return Ok(StepOutcome::WatchHit(watch));
+        }
+        if self.breakpoints.hit(self.memory.pc) {
+            log::trace!("breakpoint at {:#05X} hit", self.memory.pc);
+            return Ok(StepOutcome::BreakpointHit(self.memory.pc));
         }
 
-        result.memory.dt = 7;
-        result.memory.st -= 3;
+        Ok(StepOutcome::Executed)
+    }
 
-        assert_eq!(target, result);
-        Ok(())
+    /// Decode the instruction at the program counter, without advancing it.
+    fn fetch(&self) -> Result<Instruction, ParseError> {
+        let opcode = Opcode::from(self.memory.word(self.memory.pc));
+        let operand = self.memory.word(self.memory.pc.wrapping_add(2));
+        Instruction::decode(opcode, operand)
     }
 
-    #[rstest]
-    fn advance_timer_waiting_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
-        target.memory.dt = 10;
-        target.state = State::WaitingForKey { vx: 0x0 };
-        for _ in 0..3 {
-            target.advance_timer();
+    /// Perform an update of the timer.
+    ///
+    /// Should be called at a fixed rate of 60 hz.
+    /// The constant is [`Chip8::FREQUENCY_TIMER_UPDATE`]
+    ///
+    /// The delay and sound timers are left as they are while frozen with [`Chip8::set_timers_frozen`],
+    /// everything else that happens each frame still does.
+    pub fn advance_timer(&mut self) {
+        if !self.timers_frozen {
+            let beeping = self.is_beeping();
+            self.memory.advance_timer();
+            log::trace!("timer tick: dt {}, st {}", self.memory.dt, self.memory.st);
+            if beeping && !self.is_beeping() {
+                self.events.emit(Chip8Event::SoundStop);
+            }
         }
+        self.draw_stats = mem::take(&mut self.draw_stats_pending);
+        self.key_buffer.clear();
 
-        result.state = State::WaitingForKey { vx: 0x0 };
-        result.memory.dt = 7;
-        result.memory.st -= 3;
-
-        assert_eq!(target, result);
-        Ok(())
+        if self.state == State::WaitingForVblank {
+            log::trace!("vertical blank, resuming execution");
+            self.state = State::Ready;
+        }
+        if let (State::WaitingForKey { vx }, Some(timeout)) =
+            (self.state, self.config.wait_key_timeout_frames)
+        {
+            self.wait_key_frames += 1;
+            if self.wait_key_frames >= timeout {
+                let key = self.config.wait_key_timeout_key & 0xF;
+                log::trace!("no key in {timeout} frames, storing {key:X} in V{vx:X}");
+                self.memory.v[vx] = key;
+                self.state = State::Ready;
+            }
+        }
     }
 
-    #[rstest]
-    fn press_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
+    /// Perform several fetch decode execute cycles in a row.
+    ///
+    /// Does not sleep or spawn threads, so a frontend without them (like a browser `requestAnimationFrame` loop)
+    /// can drive the emulator by calling this once per frame with the amount of instructions that fit into it.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many cycles to perform.
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if an instruction did not execute correctly.
+    /// The remaining cycles are not performed.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the last performed cycle, [`StepOutcome::Blocked`] if none were performed.
+    /// Stops early on [`StepOutcome::WatchHit`] and [`StepOutcome::BreakpointHit`].
+    /// [`Memory::written`] is reset to the bytes written by all of the cycles.
+    pub fn tick_instructions(&mut self, n: usize) -> Result<StepOutcome, InstructionError> {
+        self.memory.written.clear();
+        let mut outcome = StepOutcome::Blocked;
+        for _ in 0..n {
+            outcome = self.step()?;
+            if outcome.is_hit() {
+                break;
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Perform fetch decode execute cycles until they cost a given amount of machine cycles, see [`Instruction::cycles`].
+    ///
+    /// Unlike [`Chip8::tick_instructions`], slow instructions like `Dxyn` leave less time for the rest of the frame,
+    /// like they did on the COSMAC VIP.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles` - How many machine cycles to spend, usually [`Chip8::CYCLES_PER_FRAME`].
+    ///   The last instruction may go over it.
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if an instruction did not execute correctly.
+    /// The remaining cycles are not performed.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the last performed cycle, [`StepOutcome::Blocked`] if none were performed.
+    /// Stops early on [`StepOutcome::WatchHit`], [`StepOutcome::BreakpointHit`], and once the execution is blocked, since waiting spends the rest of the budget.
+    /// [`Memory::written`] is reset to the bytes written by all of the cycles.
+    pub fn tick_cycles(&mut self, cycles: u32) -> Result<StepOutcome, InstructionError> {
+        self.memory.written.clear();
+        let mut spent = 0;
+        let mut outcome = StepOutcome::Blocked;
+        while spent < cycles {
+            let cost = self.fetch().map_or(0, |instruction| instruction.cycles());
+            outcome = self.step()?;
+            match outcome {
+                StepOutcome::Executed => spent += cost,
+                StepOutcome::Blocked | StepOutcome::WatchHit(_) | StepOutcome::BreakpointHit(_) => {
+                    break
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Perform a single update of the timer.
+    ///
+    /// Thread-free counterpart of [`Chip8::tick_instructions`], should be called once per 60 hz frame.
+    pub fn tick_timer(&mut self) {
+        self.advance_timer();
+    }
+
+    /// Whether the execution is blocked by `Fx0A` until a key is released.
+    pub fn is_waiting_for_key(&self) -> bool {
+        self.waiting_register().is_some()
+    }
+
+    /// Whether the execution is blocked until the next timer update,
+    /// either by the delay timer or by `Dxyn` waiting for the vertical blank.
+    pub fn is_waiting_for_timer(&self) -> bool {
+        self.state == State::WaitingForVblank || (self.memory.dt > 0 && !self.timers_frozen)
+    }
+
+    /// Stop or resume the delay and sound timers while instructions keep running, to debug timing.
+    ///
+    /// A frozen delay timer above 0 does not block the execution, since it would never run out.
+    /// Kept when a ROM is loaded or a checkpoint is restored.
+    pub fn set_timers_frozen(&mut self, frozen: bool) {
+        self.timers_frozen = frozen;
+    }
+
+    /// Whether the timers were stopped with [`Chip8::set_timers_frozen`].
+    pub fn timers_frozen(&self) -> bool {
+        self.timers_frozen
+    }
+
+    /// Whether `00FD` stopped the execution, instructions do nothing until a ROM is loaded.
+    pub fn is_halted(&self) -> bool {
+        self.state == State::Halted
+    }
+
+    /// Index of the register that will store the key `Fx0A` is waiting for.
+    pub fn waiting_register(&self) -> Option<usize> {
+        match self.state {
+            State::WaitingForKey { vx } => Some(vx),
+            _ => None,
+        }
+    }
+
+    /// Read a byte from RAM.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the byte, returns [`None`] if it is outside of RAM.
+    pub fn peek(&self, addr: u16) -> Option<u8> {
+        self.memory.ram.get(addr as usize).copied()
+    }
+
+    /// Write a byte into RAM.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the byte.
+    /// * `value` - Value to write.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if the address is outside of RAM.
+    pub fn poke(&mut self, addr: u16, value: u8) -> Result<(), InstructionError> {
+        let byte = self
+            .memory
+            .ram
+            .get_mut(addr as usize)
+            .ok_or(ExecuteError::InvalidAddress(addr))?;
+        *byte = value;
+
+        Ok(())
+    }
+
+    /// Read a general purpose register.
+    ///
+    /// # Arguments
+    ///
+    /// * `vx` - Index of the register, returns [`None`] if it is above 0xF.
+    pub fn register(&self, vx: usize) -> Option<u8> {
+        self.memory.v.get(vx).copied()
+    }
+
+    /// Write a general purpose register.
+    ///
+    /// **NOTE:** `VF` is also the flag register, writing it directly overrides the carry, borrow, or collision
+    /// flag that the last instruction stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `vx` - Index of the register.
+    /// * `value` - Value to write.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if the index is above 0xF.
+    pub fn set_register(&mut self, vx: usize, value: u8) -> Result<(), InstructionError> {
+        let register = self
+            .memory
+            .v
+            .get_mut(vx)
+            .ok_or(ExecuteError::InvalidRegister(vx))?;
+        *register = value;
+
+        Ok(())
+    }
+
+    /// Stop the execution when an instruction changes a register or a byte of RAM.
+    /// [`Chip8::advance_instruction`] then returns [`StepOutcome::WatchHit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `watch` - Location to watch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if the register index is above 0xF or the address is outside of RAM.
+    pub fn add_watch(&mut self, watch: Watch) -> Result<(), InstructionError> {
+        match watch {
+            Watch::Register(vx) if vx >= Memory::SIZE_REGISTERS => {
+                return Err(ExecuteError::InvalidRegister(vx).into());
+            }
+            Watch::Ram(addr) if addr as usize >= self.ram_size() => {
+                return Err(ExecuteError::InvalidAddress(addr).into());
+            }
+            _ => {}
+        }
+        self.watches.add(watch, &self.memory);
+
+        Ok(())
+    }
+
+    /// Stop watching a location added with [`Chip8::add_watch`].
+    ///
+    /// # Arguments
+    ///
+    /// * `watch` - Location to stop watching.
+    pub fn remove_watch(&mut self, watch: Watch) {
+        self.watches.remove(watch);
+    }
+
+    /// Stop the execution when an instruction moves the program counter to an address.
+    /// [`Chip8::advance_instruction`] then returns [`StepOutcome::BreakpointHit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Address of the instruction to stop before.
+    /// * `one_shot` - Whether the breakpoint is removed after it stops the execution once, like for running to a cursor.
+    pub fn add_breakpoint(&mut self, address: u16, one_shot: bool) {
+        self.breakpoints.add(address, one_shot);
+    }
+
+    /// Remove a breakpoint added with [`Chip8::add_breakpoint`].
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Address of the breakpoint.
+    pub fn remove_breakpoint(&mut self, address: u16) {
+        self.breakpoints.remove(address);
+    }
+
+    /// Memory pointer register `I`.
+    pub fn i(&self) -> u16 {
+        self.memory.i
+    }
+
+    /// Program counter.
+    pub fn pc(&self) -> u16 {
+        self.memory.pc
+    }
+
+    /// Delay timer.
+    pub fn dt(&self) -> u8 {
+        self.memory.dt
+    }
+
+    /// Sound timer.
+    pub fn st(&self) -> u8 {
+        self.memory.st
+    }
+
+    /// Emulate a single 60 hz frame: perform several fetch decode execute cycles and then update the timer once.
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions_per_frame` - How many cycles to perform,
+    ///   usually instructions per second divided by [`Chip8::FREQUENCY_TIMER_UPDATE`].
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if an instruction did not execute correctly.
+    /// The remaining cycles and the timer update are not performed.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the last performed cycle, like [`Chip8::tick_instructions`].
+    /// The timer is not updated on [`StepOutcome::WatchHit`] and [`StepOutcome::BreakpointHit`].
+    pub fn advance_frame(
+        &mut self,
+        instructions_per_frame: usize,
+    ) -> Result<StepOutcome, InstructionError> {
+        let outcome = self.tick_instructions(instructions_per_frame)?;
+        if outcome.is_hit() {
+            return Ok(outcome);
+        }
+        self.advance_timer();
+
+        Ok(outcome)
+    }
+
+    /// Emulate a single 60 hz frame with a budget of machine cycles instead of an instruction count,
+    /// like [`Chip8::advance_frame`] with [`Chip8::tick_cycles`].
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles_per_frame` - How many machine cycles to spend, usually [`Chip8::CYCLES_PER_FRAME`].
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if an instruction did not execute correctly.
+    /// The remaining cycles and the timer update are not performed.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the last performed cycle.
+    /// The timer is not updated on [`StepOutcome::WatchHit`] and [`StepOutcome::BreakpointHit`].
+    pub fn advance_frame_cycles(
+        &mut self,
+        cycles_per_frame: u32,
+    ) -> Result<StepOutcome, InstructionError> {
+        let outcome = self.tick_cycles(cycles_per_frame)?;
+        if outcome.is_hit() {
+            return Ok(outcome);
+        }
+        self.advance_timer();
+
+        Ok(outcome)
+    }
+
+    /// Run the program until it waits for a key press, without a frontend.
+    ///
+    /// Whenever the execution waits for the timers, they are updated instead, as if a frame has passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_steps` - How many cycles and timer updates to perform at most,
+    ///   so programs that never wait for a key do not run forever.
+    ///
+    /// # Errors
+    ///
+    /// Return an [`InstructionError`] if an instruction did not execute correctly.
+    pub fn run_until_key_wait(&mut self, max_steps: usize) -> Result<RunOutcome, InstructionError> {
+        for _ in 0..max_steps {
+            if self.is_waiting_for_key() {
+                return Ok(RunOutcome::WaitingForKey);
+            }
+            if self.is_waiting_for_timer() {
+                self.advance_timer();
+            } else {
+                match self.advance_instruction()? {
+                    StepOutcome::WatchHit(watch) => return Ok(RunOutcome::WatchHit(watch)),
+                    StepOutcome::BreakpointHit(address) => {
+                        return Ok(RunOutcome::BreakpointHit(address))
+                    }
+                    StepOutcome::Executed | StepOutcome::Blocked => {}
+                }
+            }
+        }
+
+        if self.is_waiting_for_key() {
+            Ok(RunOutcome::WaitingForKey)
+        } else {
+            Ok(RunOutcome::Stalled)
+        }
+    }
+
+    /// Presses a key by the index.
+    /// Also unblocks the execution if the system was waiting for a key press and
+    /// [`Config::wait_key_on_release`] is disabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The index of the key. Must be between 0x0 and 0xF (inclusive).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if the provided key is outside the valid range.
+    pub fn press_key(&mut self, key: u8) -> Result<(), InstructionError> {
+        if key > 0xF {
+            return Err(ExecuteError::InvalidKey(key).into());
+        }
+
+        let changed = !self.memory.keys[key as usize];
+        self.memory.keys[key as usize] = true;
+        log::trace!("key {key:X} pressed");
+
+        if !self.config.wait_key_on_release {
+            self.latch_key(key, changed);
+        }
+
+        Ok(())
+    }
+
+    /// Unpress a key by the index.
+    /// Also unblocks the execution if the system was waiting for a key press and
+    /// [`Config::wait_key_on_release`] is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The index of the key. Must be between 0x0 and 0xF (inclusive).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if the provided key is outside the valid range.
+    pub fn unpress_key(&mut self, key: u8) -> Result<(), InstructionError> {
+        if key > 0xF {
+            return Err(ExecuteError::InvalidKey(key).into());
+        }
+
+        let changed = self.memory.keys[key as usize];
+        self.memory.keys[key as usize] = false;
+        log::trace!("key {key:X} released");
+
+        if self.config.wait_key_on_release {
+            self.latch_key(key, changed);
+        }
+
+        Ok(())
+    }
+
+    /// Replace the state of all keys at once.
+    /// Also unblocks the execution if the system was waiting for a key press and a key was released
+    /// (or pressed if [`Config::wait_key_on_release`] is disabled).
+    ///
+    /// If several keys change at once, the one with the lowest index is stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Whether each key from 0x0 to 0xF is pressed.
+    pub fn set_keys(&mut self, keys: [bool; Memory::SIZE_KEYS]) {
+        let on_release = self.config.wait_key_on_release;
+        let changed = iter::zip(self.memory.keys, keys)
+            .position(|(old, new)| old == on_release && new != on_release);
+
+        self.memory.keys = keys;
+
+        if let Some(key) = changed {
+            self.latch_key(key as u8, true);
+        }
+    }
+
+    /// Keys that are held down, from 0x0 to 0xF.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = u8> + '_ {
+        self.memory
+            .keys
+            .iter()
+            .enumerate()
+            .filter(|(_, &pressed)| pressed)
+            .map(|(key, _)| key as u8)
+    }
+
+    /// Store the key and unblock the execution if the system was waiting for a key press.
+    /// Otherwise, remember the key for a `Fx0A` later in the frame if it actually changed.
+    fn latch_key(&mut self, key: u8, changed: bool) {
+        if let State::WaitingForKey { vx } = self.state {
+            log::trace!("key {key:X} stored in V{vx:X}, resuming execution");
+            self.memory.v[vx] = key;
+            self.state = State::Ready;
+        } else if changed && self.key_buffer.len() < Self::KEY_BUFFER_CAPACITY {
+            self.key_buffer.push_back(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[fixture]
+    fn target(#[default(Config::default())] config: Config) -> Chip8 {
+        let mut chip = Chip8::new(config);
+
+        chip.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4].copy_from_slice(&[
+            0x61, 0x02, // Load 2 into register 1
+            0x71, 0x03, // Add 3 to it
+        ]);
+        chip.memory.vram[0] = Vram::FULL_ROW;
+        chip.memory.stack.push(Memory::INDEX_PROGRAM_START);
+        chip.memory.dt = 0;
+        chip.memory.st = 10;
+        chip.memory.i = 100;
+        chip.memory.v = [0, 1, 2, 3, 4, 5, 31, 59, 0, 1, 2, 3, 4, 5, 30, 60];
+        chip.memory.keys = [
+            true, false, true, false, true, false, true,
+            false, // First 8 are pressed and unpressed
+            false, false, false, false, false, false, false, false, // Last 8 are not pressed
+        ];
+
+        chip
+    }
+
+    #[fixture]
+    fn result(target: Chip8) -> Chip8 {
+        target.clone()
+    }
+
+    #[rstest]
+    fn frame_buffer_returns_vram(mut target: Chip8) -> Result<()> {
+        target.memory.vram[5] = Vram::pixel_mask(7);
+
+        let mut result = [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+        result[0] = [true; Memory::SIZE_DISPLAY_WIDTH];
+        result[5][7] = true;
+
+        assert_eq!(target.frame_buffer(), result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_font_is_used_by_fx29(
+        mut target: Chip8,
+        #[values(0x0, 0x7, 0xF)] character: u8,
+    ) -> Result<()> {
+        let font = core::array::from_fn(|c| [c as u8 * 0x10; 5]);
+        target.set_font(&font);
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..2]
+            .copy_from_slice(&[0xF0, 0x29]); // Point I at the character in V0
+        target.memory.v[0x0] = character;
+
+        target.advance_instruction()?;
+
+        assert_eq!(
+            &target.memory.ram[target.memory.i as usize..][..5],
+            &font[character as usize]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn restore_undoes_changes(mut target: Chip8, result: Chip8) -> Result<()> {
+        let checkpoint = target.checkpoint();
+
+        target.tick_instructions(2)?;
+        target.advance_timer();
+        target.load(&[0xFF; 16]);
+        target.restore(&checkpoint);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn restore_same_checkpoint_twice(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.tick_instructions(1)?;
+        let checkpoint = target.checkpoint();
+
+        target.tick_instructions(1)?;
+        target.restore(&checkpoint);
+        target.tick_instructions(1)?;
+        target.restore(&checkpoint);
+
+        result.tick_instructions(1)?;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Chip8::DEFAULT_INSTRUCTIONS_PER_SECOND, 12)]
+    #[case(600, 10)]
+    #[case(1, 1)]
+    #[case(0, 1)]
+    fn instructions_per_frame_rounds(
+        #[case] instructions_per_second: usize,
+        #[case] result: usize,
+    ) -> Result<()> {
+        assert_eq!(
+            Chip8::instructions_per_frame(instructions_per_second),
+            result
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn instructions_per_frame_default_keeps_rate() -> Result<()> {
+        let per_frame = Chip8::instructions_per_frame(Chip8::DEFAULT_INSTRUCTIONS_PER_SECOND);
+        let per_second = per_frame * Chip8::FREQUENCY_TIMER_UPDATE;
+
+        assert!(
+            per_second.abs_diff(Chip8::DEFAULT_INSTRUCTIONS_PER_SECOND)
+                <= Chip8::FREQUENCY_TIMER_UPDATE / 2
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_rom_loads_program() -> Result<()> {
+        let rom = [0x61, 0x02, 0x71, 0x03];
+        let config = Config {
+            display_wait: false,
+            ..Config::default()
+        };
+
+        let target = Chip8::from_rom(&rom, config.clone())?;
+
+        let mut result = Chip8::new(config);
+        result.load(&rom);
+
+        assert_eq!(target, result);
+        assert_eq!(
+            Chip8::try_from(rom.as_slice())?,
+            Chip8::from_rom(&rom, Config::default())?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_rom_fills_ram() -> Result<()> {
+        let rom = vec![0xAB; Memory::SIZE_RAM - Memory::INDEX_PROGRAM_START as usize];
+
+        let target = Chip8::from_rom(&rom, Config::default())?;
+
+        assert_eq!(target.peek((Memory::SIZE_RAM - 1) as u16), Some(0xAB));
+        Ok(())
+    }
+
+    #[rstest]
+    fn with_ram_size_loads_large_rom() -> Result<()> {
+        let mut rom = vec![0; 8 * 1024];
+        rom[..6].copy_from_slice(&[
+            0xF0, 0x00, 0x21,
+            0xFF, // Point I at the last byte of the ROM, past the first 8 KB
+            0xF0, 0x65, // Load register 0 from I
+        ]);
+        rom[8 * 1024 - 1] = 0xAB;
+        let mut target = Chip8::default().with_ram_size(Memory::SIZE_RAM_MAX)?;
+
+        assert_eq!(target.ram_size(), 64 * 1024);
+
+        target.try_load(&rom)?;
+        target.tick_instructions(2)?;
+
+        assert_eq!(target.i(), 0x21FF);
+        assert_eq!(target.register(0x0), Some(0xAB));
+        assert_eq!(target.peek(0xFFFF), Some(0x00));
+        Ok(())
+    }
+
+    #[rstest]
+    fn with_ram_size_small_wraps() -> Result<()> {
+        let mut target = Chip8::default().with_ram_size(0x400)?;
+        target.load(&[0x12, 0x02, 0x61, 0x02]); // Jump to the next instruction, load 2 into register 1
+
+        target.memory.set_pc(0x402);
+
+        assert_eq!(target.pc(), 0x002);
+        assert_eq!(target.peek(0x400), None);
+        assert_eq!(
+            target.try_load(&[0; 0x201]),
+            Err(LoadError::OutOfBounds {
+                address: Memory::INDEX_PROGRAM_START,
+                len: 0x201,
+            })
+        );
+        assert_eq!(target.peek(0x200), Some(0x12));
+        Ok(())
+    }
+
+    #[rstest]
+    fn with_ram_size_invalid(#[values(0, 0x200, 0x10001)] size: usize) -> Result<()> {
+        assert_eq!(
+            Chip8::default().with_ram_size(size),
+            Err(LoadError::InvalidRamSize(size))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn with_state_keeps_memory() -> Result<()> {
+        let mut memory = Memory::default();
+        memory.v[0x1] = 0x02;
+        memory.stack.push(0x300);
+        memory.pc = 0x202;
+        memory.i = 0x123;
+        memory.st = 10;
+        memory.ram[0x202..0x204].copy_from_slice(&[0x71, 0x03]); // Add 3 to register 1
+
+        let mut target = Chip8::with_state(Config::cosmac_vip(), memory.clone())?;
+        target.advance_instruction()?;
+
+        assert_eq!(target.register(0x1), Some(0x05));
+        assert_eq!(target.pc(), 0x204);
+        assert_eq!(target.i(), 0x123);
+        assert_eq!(target.st(), 10);
+        assert_eq!(target.memory().stack, [0x300]);
+        assert_eq!(target.config, Config::cosmac_vip());
+        Ok(())
+    }
+
+    #[rstest]
+    fn with_state_invalid_ram_size() -> Result<()> {
+        let memory = Memory {
+            ram: vec![0; 0x10],
+            ..Memory::default()
+        };
+
+        assert_eq!(
+            Chip8::with_state(Config::default(), memory),
+            Err(LoadError::InvalidRamSize(0x10))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_rom_oversized() -> Result<()> {
+        let len = Memory::SIZE_RAM - Memory::INDEX_PROGRAM_START as usize + 1;
+
+        assert_eq!(
+            Chip8::from_rom(&vec![0; len], Config::default()),
+            Err(LoadError::OutOfBounds {
+                address: Memory::INDEX_PROGRAM_START,
+                len,
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn load_at_program_start() -> Result<()> {
+        let mut target = Chip8::new(Config {
+            program_start: 0x600,
+            ..Config::default()
+        });
+
+        target.load(&[0x61, 0x02]); // Load 2 into register 1
+
+        assert_eq!(target.pc(), 0x600);
+        assert_eq!(target.program_start(), 0x600);
+        assert_eq!(target.peek(Memory::INDEX_PROGRAM_START), Some(0x00));
+        assert_eq!(target.peek(Memory::INDEX_FONT_START as u16), Some(0xF0));
+
+        target.advance_instruction()?;
+
+        assert_eq!(target.register(0x1), Some(0x02));
+        assert_eq!(target.pc(), 0x602);
+        Ok(())
+    }
+
+    #[rstest]
+    fn new_starts_at_program_start() -> Result<()> {
+        let config = Config {
+            program_start: 0x600,
+            ..Config::default()
+        };
+
+        assert_eq!(Chip8::new(config.clone()).pc(), 0x600);
+        assert_eq!(Chip8::new(config).with_ram_size(0x1000)?.pc(), 0x600);
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_rom_program_start_in_font(#[values(0x000, 0x050, 0x1FF)] start: u16) -> Result<()> {
+        let config = Config {
+            program_start: start,
+            ..Config::default()
+        };
+        let mut target = Chip8::new(config);
+        let result = target.clone();
+
+        assert_eq!(
+            target.try_load(&[0x61, 0x02]),
+            Err(LoadError::InvalidProgramStart(start))
+        );
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_rom_oversized_at_program_start() -> Result<()> {
+        let config = Config {
+            program_start: 0x600,
+            ..Config::default()
+        };
+        let len = Memory::SIZE_RAM - 0x600 + 1;
+
+        assert_eq!(
+            Chip8::from_rom(&vec![0; len], config.clone()),
+            Err(LoadError::OutOfBounds {
+                address: 0x600,
+                len,
+            })
+        );
+        assert!(Chip8::from_rom(&vec![0; len - 1], config).is_ok());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn load_at_top_of_ram(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.load_at(0xFFE, &[0xAB, 0xCD])?;
+
+        result.memory.ram[0xFFE..].copy_from_slice(&[0xAB, 0xCD]);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn load_at_overlapping_program(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.load_at(0x1FF, &[0xAB, 0xCD, 0xEF])?;
+
+        result.memory.ram[0x1FF..][..3].copy_from_slice(&[0xAB, 0xCD, 0xEF]);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn load_at_out_of_bounds(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0xFFF, 0x1000, 0xFFFF)] addr: u16,
+    ) -> Result<()> {
+        assert_eq!(
+            target.load_at(addr, &[0xAB, 0xCD]),
+            Err(LoadError::OutOfBounds {
+                address: addr,
+                len: 2
+            })
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_ready(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        result.memory.v[1] = 5;
+        result.memory.pc += 4;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_long_operand(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        let rom = [
+            0xF0, 0x00, 0xAB, 0xCD, // Load 0xABCD into I
+            0x61, 0x07, // Load 7 into register 1
+        ];
+        target.load_at(Memory::INDEX_PROGRAM_START, &rom)?;
+
+        target.advance_instruction()?;
+
+        result.load_at(Memory::INDEX_PROGRAM_START, &rom)?;
+        result.memory.i = 0xABCD;
+        result.memory.pc += 4;
+
+        assert_eq!(target, result);
+
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory.v[1], 7);
+        assert_eq!(target.memory.pc, Memory::INDEX_PROGRAM_START + 6);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_waiting_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.state = State::WaitingForKey { vx: 0x0 };
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        result.state = State::WaitingForKey { vx: 0x0 };
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_exit_halts(mut target: Chip8) -> Result<()> {
+        target.load(&[
+            0x00, 0xFD, // Exit
+            0x61, 0x02, // Load 2 into register 1
+        ]);
+        let mut result = target.clone();
+
+        assert_eq!(target.advance_instruction()?, StepOutcome::Executed);
+        assert!(target.is_halted());
+
+        let halted = target.clone();
+        assert_eq!(target.advance_instruction()?, StepOutcome::Blocked);
+        assert_eq!(target.tick_instructions(10)?, StepOutcome::Blocked);
+
+        result.memory.pc = Memory::INDEX_PROGRAM_START + 2;
+        result.state = State::Halted;
+
+        assert_eq!(target, halted);
+        assert_eq!(target, result);
+
+        target.load(&[0x61, 0x02]);
+
+        assert!(!target.is_halted());
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_waiting_dt(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.dt = 10;
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        result.memory.dt = 10;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_display_wait_defers_second_draw(
+        #[with(Config { display_wait: true, ..Config::default() })] mut target: Chip8,
+    ) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4].copy_from_slice(&[
+            0xD0, 0x01, // Draw 1 row at V0, V0
+            0xD8, 0x81, // Draw 1 row at V8, V8
+        ]);
+        target.memory.ram[target.memory.i as usize] = 0b10000000;
+        target.memory.vram[0] = 0;
+
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory.pc, Memory::INDEX_PROGRAM_START + 2);
+        assert_eq!(target.state, State::WaitingForVblank);
+        assert!(target.memory.vram.pixel(0, 0));
+
+        target.advance_timer();
+        target.memory.vram[0] = 0;
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory.pc, Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.state, State::WaitingForVblank);
+        assert!(target.memory.vram.pixel(0, 0));
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_wraps_pc(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.ram[Memory::SIZE_RAM - 2..].copy_from_slice(&[0x61, 0x07]); // Load 7 into register 1
+        target.memory.ram[..2].copy_from_slice(&[0x71, 0x01]); // Add 1 to it
+        target.memory.pc = (Memory::SIZE_RAM - 2) as u16;
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        result.memory.ram.clone_from(&target.memory.ram);
+        result.memory.v[1] = 8;
+        result.memory.pc = 0x002;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_fetches_across_end_of_ram(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::SIZE_RAM - 1] = 0x61;
+        target.memory.ram[0] = 0x09; // Load 9 into register 1
+        target.memory.pc = (Memory::SIZE_RAM - 1) as u16;
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory.v[1], 9);
+        assert_eq!(target.memory.pc, 0x001);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_reports_parse_error_address(mut target: Chip8) -> Result<()> {
+        target.memory.ram[0x2A6..][..2].copy_from_slice(&[0xFF, 0xFF]);
+        target.memory.pc = 0x2A6;
+
+        assert_eq!(
+            target.advance_instruction(),
+            Err(InstructionError::Parse {
+                error: ParseError::UnknownOpcode(Opcode::from(0xFFFF)),
+                address: 0x2A6
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_events_sends_events() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x00, 0xE0, // Clear the display
+            0x60, 0x02, // Load 2 into register 0
+            0xF0, 0x18, // Start the sound timer with it
+            0xD1, 0x11, // Draw 1 row at V1, V1
+            0xD1, 0x11, // Draw it again, erasing it
+            0xF2, 0x0A, // Wait for a key
+        ]);
+        target.memory.i = Memory::INDEX_FONT_START as u16;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        target.set_events(Some(sender));
+
+        target.tick_instructions(6)?;
+        target.advance_timer();
+        target.advance_timer();
+        target.advance_timer();
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![
+                Chip8Event::DisplayCleared,
+                Chip8Event::SoundStart,
+                Chip8Event::SpriteDrawn { collision: false },
+                Chip8Event::SpriteDrawn { collision: true },
+                Chip8Event::WaitingForKey,
+                Chip8Event::SoundStop,
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_events_reports_stack_overflow() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[0x22, 0x00]); // Call itself forever
+        let (sender, receiver) = std::sync::mpsc::channel();
+        target.set_events(Some(sender));
+
+        target.tick_instructions(Memory::SIZE_STACK)?;
+
+        assert_eq!(receiver.try_recv().ok(), None);
+
+        target.advance_instruction()?;
+
+        assert_eq!(receiver.try_recv().ok(), Some(Chip8Event::StackOverflow));
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_events_none_stops_sending(mut target: Chip8) -> Result<()> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        target.set_events(Some(sender));
+        target.set_events(None);
+
+        target.memory.st = 1;
+        target.advance_timer();
+
+        assert!(receiver.try_recv().is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.dt = 10;
+        for _ in 0..3 {
+            target.advance_timer();
+        }
+
+        result.memory.dt = 7;
+        result.memory.st -= 3;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_latches_draw_stats(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..6].copy_from_slice(&[
+            0xD0, 0x01, // Draw 1 row at V0, V0
+            0xD0, 0x01, // Draw it again, erasing it
+            0xD0, 0x01, // And again
+        ]);
+        target.memory.ram[target.memory.i as usize] = 0b10000000;
+        target.memory.vram[0] = 0;
+
+        target.tick_instructions(3)?;
+
+        assert_eq!(target.draw_stats(), DrawStats::default());
+
+        target.advance_timer();
+
+        assert_eq!(
+            target.draw_stats(),
+            DrawStats {
+                sprites: 3,
+                collisions: 1
+            }
+        );
+
+        target.advance_timer();
+
+        assert_eq!(target.draw_stats(), DrawStats::default());
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_waiting_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.dt = 10;
+        target.state = State::WaitingForKey { vx: 0x0 };
+        for _ in 0..3 {
+            target.advance_timer();
+        }
+
+        result.state = State::WaitingForKey { vx: 0x0 };
+        result.memory.dt = 7;
+        result.memory.st -= 3;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_waiting_key_times_out(
+        #[with(Config {
+            wait_key_timeout_frames: Some(3),
+            wait_key_timeout_key: 0xA,
+            ..Config::default()
+        })]
+        mut target: Chip8,
+    ) -> Result<()> {
+        target.load(&[
+            0xF2, 0x0A, // Wait for a key to store in register 2
+            0x72, 0x01, // Add 1 to register 2
+        ]);
+        target.tick_instructions(2)?;
+        target.advance_timer();
+        target.advance_timer();
+
+        assert!(target.is_waiting_for_key());
+
+        target.advance_timer();
+        target.tick_instructions(1)?;
+
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.register(0x2), Some(0xB));
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_waiting_key_timeout_masks_key(
+        #[with(Config {
+            wait_key_timeout_frames: Some(1),
+            wait_key_timeout_key: 0x1A,
+            ..Config::default()
+        })]
+        mut target: Chip8,
+    ) -> Result<()> {
+        target.load(&[0xF2, 0x0A]); // Wait for a key to store in register 2
+        target.tick_instructions(1)?;
+        target.advance_timer();
+
+        assert_eq!(target.register(0x2), Some(0xA));
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_waiting_vblank(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.state = State::WaitingForVblank;
+        target.advance_timer();
+
+        result.memory.st -= 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.tick_instructions(2)?;
+
+        result.memory.v[1] = 5;
+        result.memory.pc += 4;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions_stops_on_error(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4]
+            .copy_from_slice(&[0xFF, 0xFF, 0x71, 0x03]);
+
+        assert!(target.tick_instructions(2).is_err());
+        assert_eq!(target.memory.pc, Memory::INDEX_PROGRAM_START + 2);
+        assert_eq!(target.memory.v[1], 1);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_frame(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.advance_frame(2)?;
+
+        result.memory.v[1] = 5;
+        result.memory.pc += 4;
+        result.memory.st -= 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_instruction_records_written_addresses() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0xA3, 0x00, // Point I at 0x300
+            0xF2, 0x55, // Store registers 0 to 2 at I
+            0x70, 0x05, // Add 5 to register 0
+        ]);
+
+        target.advance_instruction()?;
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory().written, vec![0x300, 0x301, 0x302]);
+
+        target.advance_instruction()?;
+
+        assert_eq!(target.memory().written, vec![]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions_records_written_addresses_of_all_cycles() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0xA3, 0x00, // Point I at 0x300
+            0xF0, 0x33, // Store the digits of register 0 at I
+            0xF1, 0x55, // Store registers 0 and 1 at I
+        ]);
+
+        target.tick_instructions(3)?;
+
+        assert_eq!(target.memory().written, vec![0x300, 0x301, 0x302]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn checksums_stable_across_clones(target: Chip8) -> Result<()> {
+        let clone = target.clone();
+
+        assert_eq!(target.ram_checksum(), clone.ram_checksum());
+        assert_eq!(target.display_checksum(), clone.display_checksum());
+        Ok(())
+    }
+
+    #[rstest]
+    fn ram_checksum_changes_after_write(mut target: Chip8) -> Result<()> {
+        let before = (target.ram_checksum(), target.display_checksum());
+
+        target.poke(0x300, 0xAB)?;
+
+        assert_ne!(target.ram_checksum(), before.0);
+        assert_eq!(target.display_checksum(), before.1);
+
+        target.poke(0x300, 0x00)?;
+
+        assert_eq!(target.ram_checksum(), before.0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn display_checksum_changes_after_draw(mut target: Chip8) -> Result<()> {
+        let before = (target.ram_checksum(), target.display_checksum());
+
+        target.set_pixel(5, 5, true)?;
+
+        assert_ne!(target.display_checksum(), before.1);
+        assert_eq!(target.ram_checksum(), before.0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn history_records_executed_instructions() -> Result<()> {
+        let mut target = Chip8::default().with_history(2);
+        target.load(&[
+            0x61, 0x02, // Load 2 into register 1
+            0x71, 0x03, // Add 3 to it
+            0x12, 0x00, // Jump to the start
+        ]);
+
+        target.tick_instructions(2)?;
+
+        assert_eq!(
+            target.history(),
+            &VecDeque::from([
+                (0x200, Instruction::SetVxWithValue { vx: 1, value: 2 }),
+                (0x202, Instruction::AddVxValue { vx: 1, value: 3 }),
+            ])
+        );
+
+        target.tick_instructions(2)?;
+
+        assert_eq!(
+            target.history(),
+            &VecDeque::from([
+                (0x204, Instruction::Jump { address: 0x200 }),
+                (0x200, Instruction::SetVxWithValue { vx: 1, value: 2 }),
+            ])
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn history_records_failed_instruction() -> Result<()> {
+        let mut target = Chip8::default().with_history(Chip8::HISTORY_CAPACITY);
+        target.load(&[
+            0x01, 0x23, // Call a machine code routine
+        ]);
+
+        assert!(target.advance_instruction().is_err());
+        assert_eq!(
+            target.history(),
+            &VecDeque::from([(0x200, Instruction::System { address: 0x123 })])
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn history_off_by_default() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[0x61, 0x02]);
+
+        target.advance_instruction()?;
+
+        assert!(target.history().is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_cycles_stops_at_budget() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x01, // Add 1 to register 0, 12 cycles
+            0x12, 0x00, // Jump to the start, 23 cycles
+        ]);
+
+        assert_eq!(target.tick_cycles(70)?, StepOutcome::Executed);
+        assert_eq!(target.register(0x0), Some(2));
+        assert_eq!(target.pc(), Memory::INDEX_PROGRAM_START);
+
+        target.tick_cycles(1)?;
+
+        assert_eq!(target.register(0x0), Some(3));
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_cycles_charges_slow_instructions() -> Result<()> {
+        let mut target = Chip8::new(Config {
+            display_wait: false,
+            ..Config::default()
+        });
+        target.load(&[
+            0xD0, 0x0F, // Draw 15 rows, 1190 cycles
+            0x12, 0x00, // Jump to the start, 23 cycles
+        ]);
+
+        target.tick_cycles(Chip8::CYCLES_PER_FRAME)?;
+
+        assert_eq!(target.draw_stats_pending.sprites, 4);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_cycles_stops_when_blocked() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0xF0, 0x0A, // Wait for a key
+        ]);
+
+        assert_eq!(target.tick_cycles(100)?, StepOutcome::Blocked);
+        assert!(target.is_waiting_for_key());
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_frame_cycles(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        // `6xnn` and `7xnn` cost 22 cycles together
+        target.advance_frame_cycles(22)?;
+
+        result.memory.v[1] = 5;
+        result.memory.pc += 4;
+        result.memory.st -= 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_until_key_wait_stops_at_key_wait() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x60, 0x03, // Load 3 into register 0
+            0xF0, 0x15, // Set delay timer to it
+            0xF1, 0x0A, // Wait for a key
+        ]);
+
+        assert_eq!(target.run_until_key_wait(100)?, RunOutcome::WaitingForKey);
+        assert_eq!(target.pc(), Memory::INDEX_PROGRAM_START + 6);
+        assert_eq!(target.dt(), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_until_key_wait_stalls_on_self_jump() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x12, 0x00, // Jump to itself
+        ]);
+
+        assert_eq!(target.run_until_key_wait(1000)?, RunOutcome::Stalled);
+        assert_eq!(target.pc(), Memory::INDEX_PROGRAM_START);
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_until_key_wait_stops_on_watch_hit() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Loop
+        ]);
+        target.add_watch(Watch::Register(0x0))?;
+
+        assert_eq!(
+            target.run_until_key_wait(1000)?,
+            RunOutcome::WatchHit(Watch::Register(0x0))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_timer(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.tick_timer();
+
+        result.advance_timer();
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn vram_borrows_display(mut target: Chip8) -> Result<()> {
+        target.memory.vram[5] = Vram::pixel_mask(7);
+
+        assert_eq!(target.vram(), &target.memory.vram);
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_beeping(mut target: Chip8, #[values(1, 10, 0xFF)] st: u8) -> Result<()> {
+        target.memory.st = st;
+
+        assert!(target.is_beeping());
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_beeping_silent(mut target: Chip8) -> Result<()> {
+        target.memory.st = 0;
+
+        assert!(!target.is_beeping());
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_beeping_tracks_sound_timer(mut target: Chip8) -> Result<()> {
+        target.memory.st = 2;
+
+        assert!(target.is_beeping());
+        target.advance_timer();
+        assert!(target.is_beeping());
+        target.advance_timer();
+        assert!(!target.is_beeping());
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_waiting_for_key(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..2]
+            .copy_from_slice(&[0xF3, 0x0A]); // Wait for a key and store it in V3
+
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.waiting_register(), None);
+
+        target.advance_instruction()?;
+
+        assert!(target.is_waiting_for_key());
+        assert_eq!(target.waiting_register(), Some(0x3));
+
+        target.unpress_key(0x2)?;
+
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.waiting_register(), None);
+        assert_eq!(target.memory.v[0x3], 0x2);
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_waiting_for_key_vblank(mut target: Chip8) -> Result<()> {
+        target.state = State::WaitingForVblank;
+
+        assert!(!target.is_waiting_for_key());
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_waiting_for_timer_delay(mut target: Chip8) -> Result<()> {
+        assert!(!target.is_waiting_for_timer());
+
+        target.memory.dt = 1;
+
+        assert!(target.is_waiting_for_timer());
+
+        target.advance_timer();
+
+        assert!(!target.is_waiting_for_timer());
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_timer_frozen_keeps_timers(mut target: Chip8) -> Result<()> {
+        target.load(&[
+            0x60, 0x05, // Load 5 into register 0
+            0xF0, 0x15, // Set delay timer to it
+            0xF0, 0x18, // Set sound timer to it
+            0x70, 0x01, // Add 1 to register 0
+        ]);
+        target.set_timers_frozen(true);
+
+        target.tick_instructions(3)?;
+        for _ in 0..3 {
+            target.advance_timer();
+        }
+
+        assert_eq!(target.dt(), 5);
+        assert_eq!(target.st(), 5);
+        assert!(target.is_beeping());
+        assert!(!target.is_waiting_for_timer());
+        assert_eq!(target.tick_instructions(1)?, StepOutcome::Executed);
+        assert_eq!(target.register(0x0), Some(6));
+
+        target.set_timers_frozen(false);
+        target.advance_timer();
+
+        assert_eq!(target.dt(), 4);
+        assert_eq!(target.st(), 4);
+        Ok(())
+    }
+
+    #[rstest]
+    fn is_waiting_for_timer_vblank(mut target: Chip8) -> Result<()> {
+        target.state = State::WaitingForVblank;
+
+        assert!(target.is_waiting_for_timer());
+
+        target.advance_timer();
+
+        assert!(!target.is_waiting_for_timer());
+        Ok(())
+    }
+
+    #[rstest]
+    fn peek_in_range(
+        mut target: Chip8,
+        #[values(0x000, Memory::INDEX_FONT_START as u16, 0x200, 0xFFF)] addr: u16,
+    ) -> Result<()> {
+        target.memory.ram[addr as usize] = 0xAB;
+
+        assert_eq!(target.peek(addr), Some(0xAB));
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn peek_out_of_range(target: Chip8, #[values(0x1000, 0xFFFF)] addr: u16) -> Result<()> {
+        assert_eq!(target.peek(addr), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn poke_in_range(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x000, Memory::INDEX_FONT_START as u16, 0x200, 0xFFF)] addr: u16,
+    ) -> Result<()> {
+        target.poke(addr, 0xAB)?;
+
+        result.memory.ram[addr as usize] = 0xAB;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn poke_out_of_range(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x1000, 0xFFFF)] addr: u16,
+    ) -> Result<()> {
+        assert_eq!(
+            target.poke(addr, 0xAB),
+            Err(ExecuteError::InvalidAddress(addr).into())
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn register_in_range(target: Chip8, #[values(0x0, 0x7, 0xF)] vx: usize) -> Result<()> {
+        assert_eq!(target.register(vx), Some(target.memory.v[vx]));
+        Ok(())
+    }
+
+    #[rstest]
+    fn register_out_of_range(target: Chip8, #[values(0x10, 0xFF)] vx: usize) -> Result<()> {
+        assert_eq!(target.register(vx), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_register_in_range(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x0, 0x7, 0xF)] vx: usize,
+    ) -> Result<()> {
+        target.set_register(vx, 0xAB)?;
+
+        result.memory.v[vx] = 0xAB;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_register_out_of_range(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x10, 0xFF)] vx: usize,
+    ) -> Result<()> {
+        assert_eq!(
+            target.set_register(vx, 0xAB),
+            Err(ExecuteError::InvalidRegister(vx).into())
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn add_watch_fires_once_on_change() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x05, // Add 5 to register 0
+            0x71, 0x01, // Add 1 to register 1
+            0x70, 0x05, // Add 5 to register 0
+            0x12, 0x06, // Loop forever
+        ]);
+        target.add_watch(Watch::Register(0x1))?;
+
+        let outcomes: Vec<_> = (0..8)
+            .map(|_| target.advance_instruction())
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(
+            outcomes
+                .iter()
+                .filter(|&&o| o == StepOutcome::WatchHit(Watch::Register(0x1)))
+                .count(),
+            1
+        );
+        assert_eq!(outcomes[1], StepOutcome::WatchHit(Watch::Register(0x1)));
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions_stops_on_watch_hit() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x05, // Add 5 to register 0
+            0xA3, 0x00, // Point I at 0x300
+            0xF0, 0x55, // Store register 0 at I
+            0x70, 0x05, // Add 5 to register 0
+        ]);
+        target.add_watch(Watch::Ram(0x300))?;
+
+        assert_eq!(
+            target.tick_instructions(4)?,
+            StepOutcome::WatchHit(Watch::Ram(0x300))
+        );
+        assert_eq!(target.pc(), Memory::INDEX_PROGRAM_START + 6);
+
+        target.remove_watch(Watch::Ram(0x300));
+
+        assert_eq!(target.tick_instructions(1)?, StepOutcome::Executed);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions_stops_on_one_shot_breakpoint() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Loop forever
+        ]);
+        target.add_breakpoint(Memory::INDEX_PROGRAM_START + 4, true);
+
+        assert_eq!(
+            target.tick_instructions(10)?,
+            StepOutcome::BreakpointHit(Memory::INDEX_PROGRAM_START + 4)
+        );
+        assert_eq!(target.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.register(0x0), Some(2));
+        assert_eq!(target.breakpoints, Breakpoints::default());
+
+        assert_eq!(target.tick_instructions(10)?, StepOutcome::Executed);
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_until_key_wait_stops_on_breakpoint() -> Result<()> {
+        let mut target = Chip8::default();
+        target.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Loop forever
+        ]);
+        target.add_breakpoint(Memory::INDEX_PROGRAM_START + 2, false);
+
+        assert_eq!(
+            target.run_until_key_wait(100)?,
+            RunOutcome::BreakpointHit(Memory::INDEX_PROGRAM_START + 2)
+        );
+        assert_eq!(
+            target.run_until_key_wait(100)?,
+            RunOutcome::BreakpointHit(Memory::INDEX_PROGRAM_START + 2)
+        );
+        assert_eq!(target.register(0x0), Some(2));
+
+        target.remove_breakpoint(Memory::INDEX_PROGRAM_START + 2);
+
+        assert_eq!(target.run_until_key_wait(100)?, RunOutcome::Stalled);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    #[case(Watch::Register(0x10), ExecuteError::InvalidRegister(0x10))]
+    #[case(Watch::Ram(0x1000), ExecuteError::InvalidAddress(0x1000))]
+    fn add_watch_out_of_range(
+        mut target: Chip8,
+        #[case] watch: Watch,
+        #[case] error: ExecuteError,
+    ) -> Result<()> {
+        assert_eq!(target.add_watch(watch), Err(error.into()));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(5, 7)]
+    #[case(63, 31)]
+    fn set_pixel_toggles(mut target: Chip8, #[case] x: usize, #[case] y: usize) -> Result<()> {
+        target.set_pixel(x, y, true)?;
+
+        assert_eq!(target.get_pixel(x, y), Some(true));
+        assert_eq!(target.frame_buffer()[y][x], true);
+
+        target.set_pixel(x, y, false)?;
+
+        assert_eq!(target.get_pixel(x, y), Some(false));
+        assert_eq!(target.frame_buffer()[y][x], false);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_pixel_keeps_neighbors(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.vram[3] = Vram::FULL_ROW;
+
+        target.set_pixel(10, 3, false)?;
+
+        result.memory.vram[3] = Vram::FULL_ROW & !Vram::pixel_mask(10);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(64, 0)]
+    #[case(0, 32)]
+    #[case(100, 100)]
+    fn pixel_out_of_bounds(
+        mut target: Chip8,
+        result: Chip8,
+        #[case] x: usize,
+        #[case] y: usize,
+    ) -> Result<()> {
+        assert_eq!(target.get_pixel(x, y), None);
+        assert_eq!(
+            target.set_pixel(x, y, true),
+            Err(ExecuteError::InvalidPixel(x, y).into())
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn special_registers(target: Chip8) -> Result<()> {
+        assert_eq!(target.i(), target.memory.i);
+        assert_eq!(target.pc(), target.memory.pc);
+        assert_eq!(target.dt(), target.memory.dt);
+        assert_eq!(target.st(), target.memory.st);
+        Ok(())
+    }
+
+    #[rstest]
+    fn audio_pattern_and_pitch() -> Result<()> {
+        let mut target = Chip8::default();
+
+        assert_eq!(target.audio_pattern(), &[0; Memory::SIZE_AUDIO_PATTERN]);
+        assert_eq!(target.pitch(), Memory::PITCH_DEFAULT);
+
+        target.load(&[
+            0xA2, 0x08, // Point I at the pattern
+            0xF0, 0x02, // Load the audio pattern
+            0x60, 0x70, // Load 0x70 into register 0
+            0xF0, 0x3A, // Set the pitch to it
+            0xF0, 0xF0, 0x0F, 0x0F, 0xF0, 0xF0, 0x0F, 0x0F, // Pattern
+            0xAA, 0xAA, 0x55, 0x55, 0xAA, 0xAA, 0x55, 0x55,
+        ]);
+        target.tick_instructions(4)?;
+
+        assert_eq!(
+            target.audio_pattern(),
+            &[
+                0xF0, 0xF0, 0x0F, 0x0F, 0xF0, 0xF0, 0x0F, 0x0F, 0xAA, 0xAA, 0x55, 0x55, 0xAA, 0xAA,
+                0x55, 0x55
+            ]
+        );
+        assert_eq!(target.pitch(), 0x70);
+        Ok(())
+    }
+
+    #[rstest]
+    fn press_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
         target.press_key(0xF);
 
         result.memory.keys[0xF] = true;
@@ -265,6 +2511,7 @@ mod tests {
         target.unpress_key(0x0);
 
         result.memory.keys[0x0] = false;
+        result.key_buffer.push_back(0x0);
 
         assert_eq!(target, result);
         Ok(())
@@ -286,4 +2533,219 @@ mod tests {
         assert_eq!(target, result);
         Ok(())
     }
+
+    #[rstest]
+    fn set_keys(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        let keys = [
+            false, true, false, true, false, false, false, false, // Keys 0x0 to 0x7
+            false, false, false, false, false, false, false, true, // Keys 0x8 to 0xF
+        ];
+        target.set_keys(keys);
+
+        result.memory.keys = keys;
+        result.key_buffer.push_back(0x0);
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn pressed_keys_lists_held_keys() -> Result<()> {
+        let mut target = Chip8::default();
+
+        assert_eq!(target.pressed_keys().count(), 0);
+
+        for key in [0xF, 0x3, 0xA, 0x5] {
+            target.press_key(key)?;
+        }
+        target.unpress_key(0x5)?;
+
+        assert_eq!(target.pressed_keys().collect::<Vec<_>>(), [0x3, 0xA, 0xF]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_keys_unblocks_machine_and_stores_released_key(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(1, 2)] vx: usize,
+        #[values(0x0, 0x2)] key: usize,
+    ) -> Result<()> {
+        let mut keys = target.memory.keys;
+        keys[key] = false;
+        target.state = State::WaitingForKey { vx };
+        target.set_keys(keys);
+
+        result.memory.keys = keys;
+        result.memory.v[vx] = key as u8;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_keys_stores_lowest_released_key(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        let keys = [false; Memory::SIZE_KEYS];
+        target.state = State::WaitingForKey { vx: 1 };
+        target.set_keys(keys);
+
+        result.memory.keys = keys;
+        result.memory.v[1] = 0x0;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_keys_keeps_waiting_if_nothing_released(
+        mut target: Chip8,
+        mut result: Chip8,
+    ) -> Result<()> {
+        let keys = [true; Memory::SIZE_KEYS];
+        target.state = State::WaitingForKey { vx: 1 };
+        target.set_keys(keys);
+
+        result.memory.keys = keys;
+        result.state = State::WaitingForKey { vx: 1 };
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn press_key_compat_wait_key_on_press_unblocks_machine(
+        #[with(Config { wait_key_on_release: false, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+        #[values(1, 2)] vx: usize,
+        #[values(0x1, 0xF)] key: u8,
+    ) -> Result<()> {
+        target.state = State::WaitingForKey { vx };
+        target.press_key(key)?;
+
+        result.memory.keys[key as usize] = true;
+        result.memory.v[vx] = key;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn unpress_key_compat_wait_key_on_press_keeps_waiting(
+        #[with(Config { wait_key_on_release: false, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.state = State::WaitingForKey { vx: 1 };
+        target.unpress_key(0x0)?;
+
+        result.memory.keys[0x0] = false;
+        result.state = State::WaitingForKey { vx: 1 };
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn set_keys_compat_wait_key_on_press_stores_pressed_key(
+        #[with(Config { wait_key_on_release: false, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        let keys = [true; Memory::SIZE_KEYS];
+        target.state = State::WaitingForKey { vx: 1 };
+        target.set_keys(keys);
+
+        result.memory.keys = keys;
+        result.memory.v[1] = 0x1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tap_before_wait_key_is_buffered(
+        mut target: Chip8,
+        #[values(true, false)] wait_key_on_release: bool,
+    ) -> Result<()> {
+        target.config.wait_key_on_release = wait_key_on_release;
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4].copy_from_slice(&[
+            0xF3, 0x0A, // Wait for a key and store it in V3
+            0xF4, 0x0A, // Wait for a key and store it in V4
+        ]);
+
+        for key in [0x9, 0xA] {
+            target.press_key(key)?;
+            target.unpress_key(key)?;
+        }
+        target.tick_instructions(2)?;
+
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.memory.v[0x3], 0x9);
+        assert_eq!(target.memory.v[0x4], 0xA);
+        Ok(())
+    }
+
+    #[rstest]
+    fn tap_before_wait_key_is_forgotten_after_frame(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..2]
+            .copy_from_slice(&[0xF3, 0x0A]); // Wait for a key and store it in V3
+
+        target.press_key(0x5)?;
+        target.unpress_key(0x5)?;
+        target.advance_timer();
+        target.advance_instruction()?;
+
+        assert!(target.is_waiting_for_key());
+        Ok(())
+    }
+
+    #[rstest]
+    fn tap_while_waiting_key_latches_once(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..4].copy_from_slice(&[
+            0xF3, 0x0A, // Wait for a key and store it in V3
+            0xF4, 0x0A, // Wait for a key and store it in V4
+        ]);
+        target.advance_instruction()?;
+
+        target.press_key(0x5)?;
+        assert!(target.is_waiting_for_key());
+
+        target.unpress_key(0x5)?;
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.memory.v[0x3], 0x5);
+
+        target.advance_instruction()?;
+        assert!(target.is_waiting_for_key());
+        Ok(())
+    }
+
+    #[rstest]
+    fn held_key_latches_on_release(mut target: Chip8) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..2]
+            .copy_from_slice(&[0xF3, 0x0A]); // Wait for a key and store it in V3
+        target.advance_instruction()?;
+
+        target.press_key(0x5)?;
+        assert!(target.is_waiting_for_key());
+
+        target.unpress_key(0x0)?; // Held before `Fx0A` executed
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.memory.v[0x3], 0x0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn held_key_compat_wait_key_on_press_needs_new_press(
+        #[with(Config { wait_key_on_release: false, ..Config::default() })] mut target: Chip8,
+    ) -> Result<()> {
+        target.memory.ram[Memory::INDEX_PROGRAM_START as usize..][..2]
+            .copy_from_slice(&[0xF3, 0x0A]); // Wait for a key and store it in V3
+        target.advance_instruction()?;
+
+        target.set_keys(target.memory.keys); // Keys held before `Fx0A` executed stay held
+        assert!(target.is_waiting_for_key());
+
+        target.press_key(0x5)?;
+        assert!(!target.is_waiting_for_key());
+        assert_eq!(target.memory.v[0x3], 0x5);
+        Ok(())
+    }
 }
@@ -0,0 +1,117 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::Memory;
+
+/// Location in memory whose changes stop the execution, see [`crate::Chip8::add_watch`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Watch {
+    /// General purpose register `Vx`.
+    Register(usize),
+    /// Byte of RAM.
+    Ram(u16),
+}
+
+impl Watch {
+    fn read(self, memory: &Memory) -> u8 {
+        match self {
+            Watch::Register(vx) => memory.v[vx],
+            Watch::Ram(addr) => memory.ram[addr as usize],
+        }
+    }
+}
+
+impl fmt::Display for Watch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Watch::Register(vx) => write!(f, "V{vx:X}"),
+            Watch::Ram(addr) => write!(f, "0x{addr:03X}"),
+        }
+    }
+}
+
+/// Registered watches with the values they had before the last instruction.
+///
+/// Not a part of the emulated machine, so it is ignored by serialization.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct Watches(Vec<(Watch, u8)>);
+
+impl Watches {
+    pub(crate) fn add(&mut self, watch: Watch, memory: &Memory) {
+        if !self.0.iter().any(|&(w, _)| w == watch) {
+            self.0.push((watch, watch.read(memory)));
+        }
+    }
+
+    pub(crate) fn remove(&mut self, watch: Watch) {
+        self.0.retain(|&(w, _)| w != watch);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remember the current values to compare against in [`Watches::changed`].
+    pub(crate) fn latch(&mut self, memory: &Memory) {
+        for (watch, value) in &mut self.0 {
+            *value = watch.read(memory);
+        }
+    }
+
+    /// First watch whose value is different from the latched one.
+    pub(crate) fn changed(&self, memory: &Memory) -> Option<Watch> {
+        self.0
+            .iter()
+            .find(|&&(watch, value)| watch.read(memory) != value)
+            .map(|&(watch, _)| watch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    #[case(Watch::Register(0xA), "VA")]
+    #[case(Watch::Ram(0x3F), "0x03F")]
+    fn display(#[case] watch: Watch, #[case] expected: &str) -> Result<()> {
+        assert_eq!(watch.to_string(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    fn changed_reports_modified_location() -> Result<()> {
+        let mut memory = Memory::default();
+        let mut watches = Watches::default();
+        watches.add(Watch::Register(0x1), &memory);
+        watches.add(Watch::Ram(0x300), &memory);
+
+        assert_eq!(watches.changed(&memory), None);
+
+        memory.ram[0x300] = 5;
+
+        assert_eq!(watches.changed(&memory), Some(Watch::Ram(0x300)));
+
+        watches.latch(&memory);
+
+        assert_eq!(watches.changed(&memory), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn add_ignores_duplicates() -> Result<()> {
+        let memory = Memory::default();
+        let mut watches = Watches::default();
+        watches.add(Watch::Register(0x1), &memory);
+        watches.add(Watch::Register(0x1), &memory);
+        watches.remove(Watch::Register(0x1));
+
+        assert!(watches.is_empty());
+        Ok(())
+    }
+}
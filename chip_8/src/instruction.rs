@@ -1,7 +1,9 @@
+mod disassemble;
 mod execute;
 mod opcode;
 mod parse;
 
+pub use disassemble::{disassemble, Disassembly};
 pub use execute::{ExecuteError, ExecuteInstruction};
 pub use opcode::Opcode;
 pub use parse::{Instruction, ParseError};
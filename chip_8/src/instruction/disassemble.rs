@@ -0,0 +1,192 @@
+use core::{fmt, iter};
+
+use super::*;
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Instruction::DisplayClear => write!(f, "CLS"),
+            Instruction::SubroutineReturn => write!(f, "RET"),
+            Instruction::Exit => write!(f, "EXIT"),
+            Instruction::System { address } => write!(f, "SYS 0x{address:03X}"),
+            Instruction::Jump { address } => write!(f, "JP 0x{address:03X}"),
+            Instruction::SubroutineCall { address } => write!(f, "CALL 0x{address:03X}"),
+            Instruction::SkipIfVxEqualsValue { vx, value } => {
+                write!(f, "SE V{vx:X}, 0x{value:02X}")
+            }
+            Instruction::SkipIfVxNotEqualsValue { vx, value } => {
+                write!(f, "SNE V{vx:X}, 0x{value:02X}")
+            }
+            Instruction::SkipIfVxEqualsVy { vx, vy } => write!(f, "SE V{vx:X}, V{vy:X}"),
+            Instruction::SetVxWithValue { vx, value } => write!(f, "LD V{vx:X}, 0x{value:02X}"),
+            Instruction::AddVxValue { vx, value } => write!(f, "ADD V{vx:X}, 0x{value:02X}"),
+            Instruction::SetVxWithVy { vx, vy } => write!(f, "LD V{vx:X}, V{vy:X}"),
+            Instruction::OrVxWithVy { vx, vy } => write!(f, "OR V{vx:X}, V{vy:X}"),
+            Instruction::AndVxWithVy { vx, vy } => write!(f, "AND V{vx:X}, V{vy:X}"),
+            Instruction::XorVxWithVy { vx, vy } => write!(f, "XOR V{vx:X}, V{vy:X}"),
+            Instruction::AddVxWithVy { vx, vy } => write!(f, "ADD V{vx:X}, V{vy:X}"),
+            Instruction::SubtractVxWithVy { vx, vy } => write!(f, "SUB V{vx:X}, V{vy:X}"),
+            Instruction::Shift1RightVxWithVy { vx, vy } => write!(f, "SHR V{vx:X}, V{vy:X}"),
+            Instruction::SubtractVyWithVx { vx, vy } => write!(f, "SUBN V{vx:X}, V{vy:X}"),
+            Instruction::Shift1LeftVxWithVy { vx, vy } => write!(f, "SHL V{vx:X}, V{vy:X}"),
+            Instruction::SkipIfVxNotEqualsVy { vx, vy } => write!(f, "SNE V{vx:X}, V{vy:X}"),
+            Instruction::SetIWithValue { value } => write!(f, "LD I, 0x{value:03X}"),
+            Instruction::SetIWithLongValue { value } => write!(f, "LD I, 0x{value:04X}"),
+            Instruction::JumpWithOffset { vx: _, address } => write!(f, "JP V0, 0x{address:03X}"),
+            Instruction::SetVxWithRandom { vx, value } => write!(f, "RND V{vx:X}, 0x{value:02X}"),
+            Instruction::DisplayDraw { vx, vy, height } => {
+                write!(f, "DRW V{vx:X}, V{vy:X}, 0x{height:X}")
+            }
+            Instruction::SkipIfVxKeyPressed { vx } => write!(f, "SKP V{vx:X}"),
+            Instruction::SkipIfVxKeyNotPressed { vx } => write!(f, "SKNP V{vx:X}"),
+            Instruction::SetVxWithDt { vx } => write!(f, "LD V{vx:X}, DT"),
+            Instruction::SetVxWithNextPressedKeyBlocking { vx } => write!(f, "LD V{vx:X}, K"),
+            Instruction::SetDtWithVx { vx } => write!(f, "LD DT, V{vx:X}"),
+            Instruction::SetStWithVx { vx } => write!(f, "LD ST, V{vx:X}"),
+            Instruction::AddIWithVx { vx } => write!(f, "ADD I, V{vx:X}"),
+            Instruction::SetIWithCharacterAtVx { vx } => write!(f, "LD F, V{vx:X}"),
+            Instruction::SetIWithLargeCharacterAtVx { vx } => write!(f, "LD HF, V{vx:X}"),
+            Instruction::SetAudioPatternWithI => write!(f, "LD AUDIO, [I]"),
+            Instruction::SetPitchWithVx { vx } => write!(f, "LD PITCH, V{vx:X}"),
+            Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx } => write!(f, "LD B, V{vx:X}"),
+            Instruction::StoreRegistersUntil { vx } => write!(f, "LD [I], V{vx:X}"),
+            Instruction::LoadRegistersUntil { vx } => write!(f, "LD V{vx:X}, [I]"),
+        }
+    }
+}
+
+/// Decoded word of a program.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Disassembly {
+    /// Index in RAM where the word is stored.
+    pub address: u16,
+    /// Raw 16-bit word.
+    pub word: u16,
+    /// Instruction the word decodes to, if it is a known opcode.
+    pub instruction: Option<Instruction>,
+}
+
+impl fmt::Display for Disassembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:03X}  ", self.address)?;
+        if let Some(instruction) = self.instruction {
+            write!(f, "{instruction}")
+        } else {
+            write!(f, "DB 0x{:04X}", self.word)
+        }
+    }
+}
+
+/// Decode consecutive 2-byte words of memory into instructions.
+/// The operand of `F000 NNNN` is decoded together with its opcode instead of on its own.
+///
+/// # Arguments
+///
+/// * `ram` - Memory to decode.
+/// * `start` - Index of the first word to decode.
+pub fn disassemble(ram: &[u8], start: u16) -> impl Iterator<Item = Disassembly> + '_ {
+    let word_at = |address: usize| {
+        ram.get(address..address + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    };
+
+    let mut address = start as usize;
+    iter::from_fn(move || {
+        let word = word_at(address)?;
+        let opcode = Opcode::from(word);
+        let instruction = if opcode.has_operand() {
+            word_at(address + 2).and_then(|operand| Instruction::decode(opcode, operand).ok())
+        } else {
+            Instruction::try_from(opcode).ok()
+        };
+
+        let disassembly = Disassembly {
+            address: address as u16,
+            word,
+            instruction,
+        };
+        address += instruction.map_or(2, |i| i.size() as usize);
+        Some(disassembly)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn display_formats_mnemonic(
+        #[values(
+            (0x00E0, "CLS"),
+            (0x00EE, "RET"),
+            (0x00FD, "EXIT"),
+            (0x1234, "JP 0x234"),
+            (0x2ABC, "CALL 0xABC"),
+            (0x3A0F, "SE VA, 0x0F"),
+            (0x6102, "LD V1, 0x02"),
+            (0x8AB4, "ADD VA, VB"),
+            (0x8AB6, "SHR VA, VB"),
+            (0xA123, "LD I, 0x123"),
+            (0xB300, "JP V0, 0x300"),
+            (0xD12F, "DRW V1, V2, 0xF"),
+            (0xE19E, "SKP V1"),
+            (0xF20A, "LD V2, K"),
+            (0xF430, "LD HF, V4"),
+            (0xF002, "LD AUDIO, [I]"),
+            (0xF53A, "LD PITCH, V5"),
+            (0xF355, "LD [I], V3"),
+            (0xF365, "LD V3, [I]")
+        )]
+        opcode: (u16, &str),
+    ) -> Result<()> {
+        let (opcode, mnemonic) = opcode;
+
+        assert_eq!(
+            Instruction::try_from(Opcode::from(opcode))?.to_string(),
+            mnemonic
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn disassemble_decodes_words() -> Result<()> {
+        let ram = [0x00, 0x00, 0x61, 0x02, 0xFF, 0xFF, 0x00];
+
+        assert_eq!(
+            disassemble(&ram, 2)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec!["0x002  LD V1, 0x02", "0x004  DB 0xFFFF"]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn disassemble_decodes_long_operand() -> Result<()> {
+        let ram = [0xF0, 0x00, 0x12, 0x34, 0x61, 0x02, 0xF0, 0x00];
+
+        assert_eq!(
+            disassemble(&ram, 0)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "0x000  LD I, 0x1234",
+                "0x004  LD V1, 0x02",
+                "0x006  DB 0xF000"
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn disassemble_outside_ram_is_empty() -> Result<()> {
+        let ram = [0x61, 0x02];
+
+        assert_eq!(disassemble(&ram, 4).count(), 0);
+        Ok(())
+    }
+}
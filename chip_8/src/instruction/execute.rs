@@ -1,14 +1,29 @@
 use super::*;
 use crate::chip_8::*;
+#[cfg(feature = "std")]
 use thiserror::Error;
 
 /// Errors encountered during execution of an instruction.
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
 pub enum ExecuteError {
-    #[error("instruction {0:?} is not supported")]
+    #[cfg_attr(feature = "std", error("instruction {0:?} is not supported"))]
     UnsupportedInstruction(Instruction),
-    #[error("key {0:?} is not in 0-F range")]
+    #[cfg_attr(feature = "std", error("key {0:?} is not in 0-F range"))]
     InvalidKey(u8),
+    #[cfg_attr(feature = "std", error("register {0:?} is not in 0-F range"))]
+    InvalidRegister(usize),
+    #[cfg_attr(feature = "std", error("address {0:#05X} is outside of RAM"))]
+    InvalidAddress(u16),
+    #[cfg_attr(feature = "std", error("pixel ({0}, {1}) is outside of the display"))]
+    InvalidPixel(usize, usize),
+    #[cfg_attr(feature = "std", error("sprite height {0} is not in 0-15 range"))]
+    InvalidSpriteHeight(u8),
+    #[cfg_attr(
+        feature = "std",
+        error("returned from a subroutine with an empty stack")
+    )]
+    StackUnderflow,
 }
 
 pub trait ExecuteInstruction {
@@ -24,17 +39,29 @@ impl ExecuteInstruction for Chip8 {
     fn execute(&mut self, instruction: &Instruction) -> Result<(), ExecuteError> {
         let memory = &mut self.memory;
         let config = &self.config;
+        let rng = &mut self.rng;
+        let events = &self.events;
+
+        if let Some(register) = instruction
+            .registers()
+            .into_iter()
+            .flatten()
+            .find(|&r| r >= Memory::SIZE_REGISTERS)
+        {
+            return Err(ExecuteError::InvalidRegister(register));
+        }
 
         match *instruction {
             Instruction::DisplayClear => {
-                memory.clear_vram();
+                memory.vram.clear();
+                events.emit(Chip8Event::DisplayCleared);
             }
             Instruction::SubroutineReturn => {
-                if let Some(pc) = memory.stack.pop() {
-                    memory.pc = pc;
-                } else {
-                    todo!("Figure out what to do on the last return");
-                }
+                memory.pc = memory.stack.pop().ok_or(ExecuteError::StackUnderflow)?;
+            }
+            Instruction::Exit => {
+                log::trace!("exiting");
+                self.state = State::Halted;
             }
             Instruction::System { address: _ } => {
                 return Err(ExecuteError::UnsupportedInstruction(*instruction))
@@ -45,20 +72,23 @@ impl ExecuteInstruction for Chip8 {
             Instruction::SubroutineCall { address } => {
                 memory.stack.push(memory.pc);
                 memory.pc = address;
+                if memory.stack.len() > Memory::SIZE_STACK {
+                    events.emit(Chip8Event::StackOverflow);
+                }
             }
             Instruction::SkipIfVxEqualsValue { vx, value } => {
                 if memory.v[vx] == value {
-                    memory.increment_pc();
+                    memory.skip_instruction();
                 }
             }
             Instruction::SkipIfVxNotEqualsValue { vx, value } => {
                 if memory.v[vx] != value {
-                    memory.increment_pc();
+                    memory.skip_instruction();
                 }
             }
             Instruction::SkipIfVxEqualsVy { vx, vy } => {
                 if memory.v[vx] == memory.v[vy] {
-                    memory.increment_pc();
+                    memory.skip_instruction();
                 }
             }
             Instruction::SetVxWithValue { vx, value } => {
@@ -72,12 +102,21 @@ impl ExecuteInstruction for Chip8 {
             }
             Instruction::OrVxWithVy { vx, vy } => {
                 memory.v[vx] |= memory.v[vy];
+                if config.logic_resets_vf {
+                    memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+                }
             }
             Instruction::AndVxWithVy { vx, vy } => {
                 memory.v[vx] &= memory.v[vy];
+                if config.logic_resets_vf {
+                    memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+                }
             }
             Instruction::XorVxWithVy { vx, vy } => {
                 memory.v[vx] ^= memory.v[vy];
+                if config.logic_resets_vf {
+                    memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+                }
             }
             Instruction::AddVxWithVy { vx, vy } => {
                 let (result, overflow) = memory.v[vx].overflowing_add(memory.v[vy]);
@@ -90,14 +129,10 @@ impl ExecuteInstruction for Chip8 {
                 memory.v[Memory::INDEX_FLAG_REGISTER] = (!underflow).into();
             }
             Instruction::Shift1RightVxWithVy { vx, vy } => {
-                if !config.shift_ignores_vy {
-                    memory.v[vx] = memory.v[vy];
-                }
+                let value = memory.v[if config.shift_ignores_vy { vx } else { vy }];
 
-                let discarded = memory.v[vx] & 0b00000001;
-
-                memory.v[vx] >>= 1;
-                memory.v[Memory::INDEX_FLAG_REGISTER] = discarded;
+                memory.v[vx] = value >> 1;
+                memory.v[Memory::INDEX_FLAG_REGISTER] = value & 0b00000001;
             }
             Instruction::SubtractVyWithVx { vx, vy } => {
                 let (result, underflow) = memory.v[vy].overflowing_sub(memory.v[vx]);
@@ -105,60 +140,54 @@ impl ExecuteInstruction for Chip8 {
                 memory.v[Memory::INDEX_FLAG_REGISTER] = (!underflow).into();
             }
             Instruction::Shift1LeftVxWithVy { vx, vy } => {
-                if !config.shift_ignores_vy {
-                    memory.v[vx] = memory.v[vy];
-                }
+                let value = memory.v[if config.shift_ignores_vy { vx } else { vy }];
 
-                let discarded = (memory.v[vx] & 0b10000000) >> 7;
-
-                memory.v[vx] <<= 1;
-                memory.v[Memory::INDEX_FLAG_REGISTER] = discarded;
+                memory.v[vx] = value << 1;
+                memory.v[Memory::INDEX_FLAG_REGISTER] = (value & 0b10000000) >> 7;
             }
             Instruction::SkipIfVxNotEqualsVy { vx, vy } => {
                 if memory.v[vx] != memory.v[vy] {
-                    memory.increment_pc();
+                    memory.skip_instruction();
                 }
             }
-            Instruction::SetIWithValue { value } => {
+            Instruction::SetIWithValue { value } | Instruction::SetIWithLongValue { value } => {
                 memory.i = value;
             }
             Instruction::JumpWithOffset { vx, address: value } => {
                 let register_offset = memory.v[if config.jump_reads_from_vx { vx } else { 0 }];
-                memory.pc = value + register_offset as u16;
+                memory.set_pc(value + register_offset as u16);
             }
             Instruction::SetVxWithRandom { vx, value } => {
-                memory.v[vx] = rand::random::<u8>() & value;
+                memory.v[vx] = rng.next_u8() & value;
             }
             Instruction::DisplayDraw { vx, vy, height } => {
-                let x = memory.v[vx] % Memory::SIZE_DISPLAY_WIDTH as u8;
-                let y = memory.v[vy] % Memory::SIZE_DISPLAY_HEIGHT as u8;
-                memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
-                'rows: for r in 0..(height) {
-                    let row = memory.ram[(memory.i + r as u16) as usize];
-                    'pixels: for p in 0..8 {
-                        let pixel = row & (1 << (7 - p));
-                        let pixel = pixel != 0;
-                        if pixel {
-                            let x = (x + p) as usize;
-                            let y = (y + r) as usize;
-                            if x >= Memory::SIZE_DISPLAY_WIDTH {
-                                break 'pixels;
-                            }
-                            if y >= Memory::SIZE_DISPLAY_HEIGHT {
-                                break 'rows;
-                            }
-                            memory.vram[y][x] ^= pixel;
-                            if !memory.vram[y][x] {
-                                memory.v[Memory::INDEX_FLAG_REGISTER] = 1;
-                            }
-                        }
-                    }
+                if height > 0xF {
+                    return Err(ExecuteError::InvalidSpriteHeight(height));
+                }
+                let mut sprite = [0; 0xF];
+                let sprite = &mut sprite[..height as usize];
+                for (r, byte) in sprite.iter_mut().enumerate() {
+                    *byte = memory.ram[memory.i_address(r)];
+                }
+                let collision =
+                    memory.draw_sprite(memory.v[vx] as usize, memory.v[vy] as usize, sprite);
+                memory.v[Memory::INDEX_FLAG_REGISTER] = collision as u8;
+
+                self.draw_stats_pending.sprites += 1;
+                if collision {
+                    self.draw_stats_pending.collisions += 1;
+                }
+                events.emit(Chip8Event::SpriteDrawn { collision });
+
+                if config.display_wait {
+                    log::trace!("waiting for vertical blank");
+                    self.state = State::WaitingForVblank;
                 }
             }
             Instruction::SkipIfVxKeyPressed { vx } => {
                 if let Some(&key) = memory.keys.get(memory.v[vx] as usize) {
                     if key {
-                        memory.increment_pc();
+                        memory.skip_instruction();
                     }
                 } else {
                     return Err(ExecuteError::InvalidKey(memory.v[vx]));
@@ -167,7 +196,7 @@ impl ExecuteInstruction for Chip8 {
             Instruction::SkipIfVxKeyNotPressed { vx } => {
                 if let Some(&key) = memory.keys.get(memory.v[vx] as usize) {
                     if !key {
-                        memory.increment_pc();
+                        memory.skip_instruction();
                     }
                 } else {
                     return Err(ExecuteError::InvalidKey(memory.v[vx]));
@@ -177,43 +206,78 @@ impl ExecuteInstruction for Chip8 {
                 memory.v[vx] = memory.dt;
             }
             Instruction::SetVxWithNextPressedKeyBlocking { vx } => {
-                self.state = State::WaitingForKey { vx };
+                if let Some(key) = self.key_buffer.pop_front() {
+                    log::trace!("key {key:X} was buffered this frame, storing it in V{vx:X}");
+                    memory.v[vx] = key;
+                } else {
+                    log::trace!("waiting for a key to store in V{vx:X}");
+                    self.state = State::WaitingForKey { vx };
+                    self.wait_key_frames = 0;
+                    events.emit(Chip8Event::WaitingForKey);
+                }
             }
             Instruction::SetDtWithVx { vx } => {
                 memory.dt = memory.v[vx];
             }
             Instruction::SetStWithVx { vx } => {
+                match (memory.st, memory.v[vx]) {
+                    (0, 1..) => events.emit(Chip8Event::SoundStart),
+                    (1.., 0) => events.emit(Chip8Event::SoundStop),
+                    _ => (),
+                }
                 memory.st = memory.v[vx];
             }
             Instruction::AddIWithVx { vx } => {
-                memory.i += memory.v[vx] as u16;
+                let overflow = memory.i as usize + memory.v[vx] as usize >= memory.ram.len();
+                memory.i =
+                    self.config
+                        .index_overflow
+                        .add(memory.i, memory.v[vx] as u16, memory.ram.len());
 
-                if self.config.add_to_index_stores_overflow && memory.i >= 0x1000 {
+                if self.config.add_to_index_stores_overflow && overflow {
                     memory.v[Memory::INDEX_FLAG_REGISTER] = 1;
                 }
             }
             Instruction::SetIWithCharacterAtVx { vx } => {
                 memory.i = Memory::INDEX_FONT_START as u16 + memory.v[vx] as u16 * 5;
             }
+            Instruction::SetIWithLargeCharacterAtVx { vx } => {
+                memory.i = Memory::INDEX_FONT_LARGE_START as u16 + memory.v[vx] as u16 * 10;
+            }
             Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx } => {
                 let value = memory.v[vx];
 
-                memory.ram[memory.i as usize + 0] = (value / 100) % 10;
-                memory.ram[memory.i as usize + 1] = (value / 10) % 10;
-                memory.ram[memory.i as usize + 2] = (value / 1) % 10;
+                for (offset, digit) in [(value / 100) % 10, (value / 10) % 10, value % 10]
+                    .into_iter()
+                    .enumerate()
+                {
+                    memory.write(memory.i_address(offset), digit);
+                }
+            }
+            Instruction::SetAudioPatternWithI => {
+                for offset in 0..Memory::SIZE_AUDIO_PATTERN {
+                    memory.audio_pattern[offset] = memory.ram[memory.i_address(offset)];
+                }
+            }
+            Instruction::SetPitchWithVx { vx } => {
+                memory.pitch = memory.v[vx];
             }
             Instruction::StoreRegistersUntil { vx } => {
-                memory.ram[memory.i as usize..][..=vx].copy_from_slice(&memory.v[..=vx]);
+                for offset in 0..=vx {
+                    memory.write(memory.i_address(offset), memory.v[offset]);
+                }
 
                 if config.store_load_modifies_i {
-                    memory.i += vx as u16 + 1;
+                    memory.i = memory.i.wrapping_add(vx as u16 + 1);
                 }
             }
             Instruction::LoadRegistersUntil { vx } => {
-                memory.v[..=vx].copy_from_slice(&memory.ram[memory.i as usize..][..=vx]);
+                for offset in 0..=vx {
+                    memory.v[offset] = memory.ram[memory.i_address(offset)];
+                }
 
                 if config.store_load_modifies_i {
-                    memory.i += vx as u16 + 1;
+                    memory.i = memory.i.wrapping_add(vx as u16 + 1);
                 }
             }
         };
@@ -238,7 +302,7 @@ mod tests {
             0x61, 0x02, // Load 2 into register 1
             0x71, 0x03, // Add 3 to it
         ]);
-        chip.memory.vram[0].iter_mut().for_each(|e| *e = true);
+        chip.memory.vram[0] = Vram::FULL_ROW;
         chip.memory.stack.push(Memory::INDEX_PROGRAM_START);
         chip.memory.dt = 60;
         chip.memory.st = 10;
@@ -262,7 +326,17 @@ mod tests {
     fn execute_display_clear(mut target: Chip8, mut result: Chip8) -> Result<()> {
         target.execute(&Instruction::DisplayClear)?;
 
-        result.memory.vram = [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+        result.memory.vram = Vram::new();
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_exit(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.execute(&Instruction::Exit)?;
+
+        result.state = State::Halted;
 
         assert_eq!(target, result);
         Ok(())
@@ -302,6 +376,19 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_subroutine_return_empty_stack(mut target: Chip8) -> Result<()> {
+        target.memory.stack.clear();
+        let result = target.clone();
+
+        assert_eq!(
+            target.execute(&Instruction::SubroutineReturn),
+            Err(ExecuteError::StackUnderflow)
+        );
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_system_unsupported(
         mut target: Chip8,
@@ -317,6 +404,50 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_invalid_register(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x10, 0xFF)] register: usize,
+    ) -> Result<()> {
+        assert_eq!(
+            target.execute(&Instruction::SetVxWithValue {
+                vx: register,
+                value: 1
+            }),
+            Err(ExecuteError::InvalidRegister(register))
+        );
+        assert_eq!(
+            target.execute(&Instruction::SetVxWithVy {
+                vx: 0x1,
+                vy: register
+            }),
+            Err(ExecuteError::InvalidRegister(register))
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_display_draw_invalid_height(
+        mut target: Chip8,
+        result: Chip8,
+        #[values(0x10, 0xFF)] height: u8,
+    ) -> Result<()> {
+        assert_eq!(
+            target.execute(&Instruction::DisplayDraw {
+                vx: 0x0,
+                vy: 0x1,
+                height
+            }),
+            Err(ExecuteError::InvalidSpriteHeight(height))
+        );
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_jump(
         mut target: Chip8,
@@ -383,6 +514,27 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_skip_if_vx_equals_value_skips_long_instruction(
+        mut target: Chip8,
+        mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.ram[target.memory.pc as usize..][..4]
+            .copy_from_slice(&[0xF0, 0x00, 0x12, 0x34]);
+
+        target.execute(&Instruction::SkipIfVxEqualsValue {
+            vx: 1,
+            value: target.memory.v[1],
+        })?;
+
+        result.memory.ram[result.memory.pc as usize..][..4]
+            .copy_from_slice(&[0xF0, 0x00, 0x12, 0x34]);
+        result.memory.pc += 4;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_skip_if_vx_equals_value_not_equals(
         mut target: Chip8,
@@ -560,6 +712,40 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_logic_resets_vf(
+        #[values(true, false)] logic_resets_vf: bool,
+        #[with(Config { logic_resets_vf, ..Config::default() })] mut target: Chip8,
+        #[values(
+            Instruction::OrVxWithVy { vx: 1, vy: 3 },
+            Instruction::AndVxWithVy { vx: 1, vy: 3 },
+            Instruction::XorVxWithVy { vx: 1, vy: 3 }
+        )]
+        instruction: Instruction,
+    ) -> Result<()> {
+        let vf = target.memory.v[Memory::INDEX_FLAG_REGISTER];
+        target.execute(&instruction)?;
+
+        assert_eq!(
+            target.memory.v[Memory::INDEX_FLAG_REGISTER],
+            if logic_resets_vf { 0 } else { vf }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_logic_with_vf_resets_after_operation(
+        #[with(Config { logic_resets_vf: true, ..Config::default() })] mut target: Chip8,
+    ) -> Result<()> {
+        target.execute(&Instruction::OrVxWithVy {
+            vx: Memory::INDEX_FLAG_REGISTER,
+            vy: 1,
+        })?;
+
+        assert_eq!(target.memory.v[Memory::INDEX_FLAG_REGISTER], 0);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_add_vx_with_vy(
         mut target: Chip8,
@@ -724,6 +910,94 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_shift_1_right_vx_with_vy_flag_destination(
+        #[values(true, false)] shift_ignores_vy: bool,
+        #[with(Config { shift_ignores_vy, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.v[0x3] = 0b10000001;
+        target.memory.v[0xF] = 0b10000001;
+        target.execute(&Instruction::Shift1RightVxWithVy { vx: 0xF, vy: 0x3 })?;
+
+        result.memory.v[0x3] = 0b10000001;
+        result.memory.v[0xF] = 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_shift_1_left_vx_with_vy_flag_destination(
+        #[values(true, false)] shift_ignores_vy: bool,
+        #[with(Config { shift_ignores_vy, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.v[0x3] = 0b10000001;
+        target.memory.v[0xF] = 0b10000001;
+        target.execute(&Instruction::Shift1LeftVxWithVy { vx: 0xF, vy: 0x3 })?;
+
+        result.memory.v[0x3] = 0b10000001;
+        result.memory.v[0xF] = 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_add_vx_with_vy_flag_destination(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values((0x01, 0), (0xFF, 1))] case: (u8, u8),
+    ) -> Result<()> {
+        let (vy, flag) = case;
+        target.memory.v[0x3] = vy;
+        target.memory.v[0xF] = 200;
+        target.execute(&Instruction::AddVxWithVy { vx: 0xF, vy: 0x3 })?;
+
+        result.memory.v[0x3] = vy;
+        result.memory.v[0xF] = flag;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_subtract_vx_with_vy_flag_destination(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values((0x01, 1), (0xFF, 0))] case: (u8, u8),
+    ) -> Result<()> {
+        let (vy, flag) = case;
+        target.memory.v[0x3] = vy;
+        target.memory.v[0xF] = 200;
+        target.execute(&Instruction::SubtractVxWithVy { vx: 0xF, vy: 0x3 })?;
+
+        result.memory.v[0x3] = vy;
+        result.memory.v[0xF] = flag;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_subtract_vy_with_vx_flag_destination(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values((0x01, 0), (0xFF, 1))] case: (u8, u8),
+    ) -> Result<()> {
+        let (vy, flag) = case;
+        target.memory.v[0x3] = vy;
+        target.memory.v[0xF] = 200;
+        target.execute(&Instruction::SubtractVyWithVx { vx: 0xF, vy: 0x3 })?;
+
+        result.memory.v[0x3] = vy;
+        result.memory.v[0xF] = flag;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_skip_if_vx_not_equals_vy_not_equals(
         mut target: Chip8,
@@ -764,6 +1038,20 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_set_i_with_long_value(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0x123, 0xABCD)] value: u16,
+    ) -> Result<()> {
+        target.execute(&Instruction::SetIWithLongValue { value })?;
+
+        result.memory.i = value;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_jump_with_offset_compat_use_v0(
         #[with(Config { jump_reads_from_vx: false, ..Config::default() })] mut target: Chip8,
@@ -794,6 +1082,45 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case(false, 0x230 + 0x10)]
+    #[case(true, 0x230 + 0x04)]
+    fn execute_jump_with_offset_decoded_adds_register_once(
+        #[case] jump_reads_from_vx: bool,
+        #[case] pc: u16,
+    ) -> Result<()> {
+        let mut target = target(Config {
+            jump_reads_from_vx,
+            ..Config::default()
+        });
+        target.memory.v[0] = 0x10;
+        target.memory.v[2] = 0x04;
+        let mut result = target.clone();
+
+        target.execute(&Instruction::try_from(Opcode::from(0xB230))?)?;
+
+        result.memory.pc = pc;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn execute_jump_with_offset_wraps(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.v[0] = 0x10;
+        target.execute(&Instruction::JumpWithOffset {
+            vx: 0,
+            address: 0xFF8,
+        })?;
+
+        result.memory.v[0] = 0x10;
+        result.memory.pc = 0x008;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_set_vx_with_random(
         mut target: Chip8,
@@ -810,6 +1137,86 @@ mod tests {
         Ok(())
     }
 
+    /// Pack pixels starting at a column into a display row, dropping the ones past the right edge.
+    fn pack_row(x: usize, pixels: &[bool]) -> u128 {
+        pixels
+            .iter()
+            .enumerate()
+            .filter(|&(dx, &pixel)| pixel && x + dx < Memory::SIZE_DISPLAY_WIDTH)
+            .fold(0, |row, (dx, _)| row | Vram::pixel_mask(x + dx))
+    }
+
+    /// Pixel-by-pixel sprite drawing, used as a reference for the packed implementation.
+    fn draw_unpacked(
+        display: &mut [[bool; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT],
+        sprite: &[u8],
+        x: usize,
+        y: usize,
+    ) -> bool {
+        let mut collision = false;
+        'rows: for (r, row) in sprite.iter().enumerate() {
+            for p in 0..8 {
+                if row & (1 << (7 - p)) != 0 {
+                    let x = x + p;
+                    let y = y + r;
+                    if x >= Memory::SIZE_DISPLAY_WIDTH {
+                        break;
+                    }
+                    if y >= Memory::SIZE_DISPLAY_HEIGHT {
+                        break 'rows;
+                    }
+                    display[y][x] ^= true;
+                    if !display[y][x] {
+                        collision = true;
+                    }
+                }
+            }
+        }
+        collision
+    }
+
+    #[rstest]
+    fn execute_display_draw_matches_unpacked(
+        mut target: Chip8,
+        #[values(0, 1, 2, 3, 4, 5, 6, 7)] seed: u64,
+    ) -> Result<()> {
+        use rand::{rngs::SmallRng, Rng as _, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(seed);
+        for y in 0..Memory::SIZE_DISPLAY_HEIGHT {
+            target.memory.vram[y] = u128::from(rng.gen::<u64>());
+        }
+        let mut display = target.frame_buffer();
+
+        for _ in 0..64 {
+            let height = rng.gen_range(0..=15);
+            let sprite: Vec<u8> = (0..height).map(|_| rng.gen()).collect();
+            target.memory.ram[target.memory.i as usize..][..sprite.len()].copy_from_slice(&sprite);
+            target.memory.v[0] = rng.gen();
+            target.memory.v[1] = rng.gen();
+
+            target.execute(&Instruction::DisplayDraw {
+                vx: 0,
+                vy: 1,
+                height,
+            })?;
+
+            let collision = draw_unpacked(
+                &mut display,
+                &sprite,
+                target.memory.v[0] as usize % Memory::SIZE_DISPLAY_WIDTH,
+                target.memory.v[1] as usize % Memory::SIZE_DISPLAY_HEIGHT,
+            );
+
+            assert_eq!(target.frame_buffer(), display);
+            assert_eq!(
+                target.memory.v[Memory::INDEX_FLAG_REGISTER],
+                collision as u8
+            );
+        }
+        Ok(())
+    }
+
     #[rstest]
     fn execute_display_draw(
         mut target: Chip8,
@@ -819,22 +1226,21 @@ mod tests {
     ) -> Result<()> {
         let x = target.memory.v[vx] as usize;
         let y = target.memory.v[vy] as usize;
-        let width = usize::min(8, Memory::SIZE_DISPLAY_WIDTH - x);
 
-        target.memory.ram[target.memory.i as usize + 0] = 0b10111111;
+        target.memory.ram[target.memory.i as usize] = 0b10111111;
         target.memory.ram[target.memory.i as usize + 1] = 0b01001001;
 
         target.execute(&Instruction::DisplayDraw { vx, vy, height: 2 })?;
 
-        result.memory.ram[result.memory.i as usize + 0] = 0b10111111;
+        result.memory.ram[result.memory.i as usize] = 0b10111111;
         result.memory.ram[result.memory.i as usize + 1] = 0b01001001;
-        result.memory.vram[y][x..][..width]
-            .copy_from_slice(&[true, false, true, true, true, true, true, true][..width]);
+        result.memory.vram[y] = pack_row(x, &[true, false, true, true, true, true, true, true]);
         if y + 1 < Memory::SIZE_DISPLAY_HEIGHT {
-            result.memory.vram[y + 1][x..][..width]
-                .copy_from_slice(&[false, true, false, false, true, false, false, true][..width]);
+            result.memory.vram[y + 1] =
+                pack_row(x, &[false, true, false, false, true, false, false, true]);
         }
         result.memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+        result.draw_stats_pending.sprites += 1;
 
         assert_eq!(target, result);
         Ok(())
@@ -849,23 +1255,150 @@ mod tests {
     ) -> Result<()> {
         let x = target.memory.v[vx] as usize;
         let y = target.memory.v[vy] as usize;
-        let width = usize::min(8, Memory::SIZE_DISPLAY_WIDTH - x);
 
-        target.memory.ram[target.memory.i as usize + 0] = 0b10111111;
+        target.memory.ram[target.memory.i as usize] = 0b10111111;
         target.memory.ram[target.memory.i as usize + 1] = 0b01001001;
-        target.memory.vram[y][x] = true;
+        target.memory.vram[y] = Vram::pixel_mask(x);
 
         target.execute(&Instruction::DisplayDraw { vx, vy, height: 2 })?;
 
-        result.memory.ram[result.memory.i as usize + 0] = 0b10111111;
+        result.memory.ram[result.memory.i as usize] = 0b10111111;
         result.memory.ram[result.memory.i as usize + 1] = 0b01001001;
-        result.memory.vram[y][x..][..width]
-            .copy_from_slice(&[false, false, true, true, true, true, true, true][..width]);
+        result.memory.vram[y] = pack_row(x, &[false, false, true, true, true, true, true, true]);
         if y + 1 < Memory::SIZE_DISPLAY_HEIGHT {
-            result.memory.vram[y + 1][x..][..width]
-                .copy_from_slice(&[false, true, false, false, true, false, false, true][..width]);
+            result.memory.vram[y + 1] =
+                pack_row(x, &[false, true, false, false, true, false, false, true]);
         }
         result.memory.v[Memory::INDEX_FLAG_REGISTER] = 1;
+        result.draw_stats_pending.sprites += 1;
+        result.draw_stats_pending.collisions += 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn execute_display_draw_wraps_i(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        target.memory.i = 0xFFF;
+        target.memory.ram[0xFFF] = 0b10000000;
+        target.memory.ram[0x000] = 0b01000000;
+
+        target.execute(&Instruction::DisplayDraw {
+            vx: 8,
+            vy: 9,
+            height: 2,
+        })?;
+
+        result.memory.i = 0xFFF;
+        result.memory.ram[0xFFF] = 0b10000000;
+        result.memory.ram[0x000] = 0b01000000;
+        result.memory.vram[1] = Vram::pixel_mask(0);
+        result.memory.vram[2] = Vram::pixel_mask(1);
+        result.memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+        result.draw_stats_pending.sprites += 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_display_draw_clips_at_corner(
+        mut target: Chip8,
+        #[values(63, 127, 255)] x: u8,
+        #[values(31, 63, 255)] y: u8,
+    ) -> Result<()> {
+        target.memory.vram.clear();
+        target.memory.v[0] = x;
+        target.memory.v[1] = y;
+        target.memory.ram[target.memory.i as usize..][..2].copy_from_slice(&[0xFF, 0xFF]);
+
+        target.execute(&Instruction::DisplayDraw {
+            vx: 0,
+            vy: 1,
+            height: 2,
+        })?;
+
+        assert_eq!(
+            target.memory.vram.lit_pixels().collect::<Vec<_>>(),
+            vec![(63, 31)]
+        );
+        assert_eq!(target.memory.v[Memory::INDEX_FLAG_REGISTER], 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_display_draw_wraps_start_and_clips_pixels(
+        mut target: Chip8,
+        mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.v[0] = 60 + Memory::SIZE_DISPLAY_WIDTH as u8;
+        target.memory.v[1] = 30 + Memory::SIZE_DISPLAY_HEIGHT as u8;
+        target.memory.ram[target.memory.i as usize..][..3].copy_from_slice(&[0xFF, 0xFF, 0xFF]);
+
+        target.execute(&Instruction::DisplayDraw {
+            vx: 0,
+            vy: 1,
+            height: 3,
+        })?;
+
+        result.memory.v[0] = target.memory.v[0];
+        result.memory.v[1] = target.memory.v[1];
+        result.memory.ram[result.memory.i as usize..][..3].copy_from_slice(&[0xFF, 0xFF, 0xFF]);
+        result.memory.vram[30] = pack_row(60, &[true; 4]);
+        result.memory.vram[31] = pack_row(60, &[true; 4]);
+        result.memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+        result.draw_stats_pending.sprites += 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_display_draw_clips_tall_sprite(
+        mut target: Chip8,
+        #[values(0, 17, 20, 31)] y: u8,
+    ) -> Result<()> {
+        target.memory.v[0] = 0;
+        target.memory.v[1] = y;
+        target.memory.ram[target.memory.i as usize..][..15].fill(0b10000000);
+        target.memory.vram.clear();
+        target.memory.vram[0] = Vram::pixel_mask(0);
+
+        target.execute(&Instruction::DisplayDraw {
+            vx: 0,
+            vy: 1,
+            height: 15,
+        })?;
+
+        let drawn = usize::min(15, Memory::SIZE_DISPLAY_HEIGHT - y as usize);
+        // The pixel that was on before is turned off only if the sprite covers it.
+        let lit: Vec<_> = (0..Memory::SIZE_DISPLAY_HEIGHT)
+            .filter(|&row| (row == 0) != (y as usize..y as usize + drawn).contains(&row))
+            .map(|row| (0, row))
+            .collect();
+        assert_eq!(target.memory.vram.lit_pixels().collect::<Vec<_>>(), lit);
+        assert_eq!(
+            target.memory.v[Memory::INDEX_FLAG_REGISTER],
+            u8::from(y == 0)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_display_draw_compat_display_wait(
+        #[with(Config { display_wait: true, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.execute(&Instruction::DisplayDraw {
+            vx: 0,
+            vy: 0,
+            height: 0,
+        })?;
+
+        result.memory.v[Memory::INDEX_FLAG_REGISTER] = 0;
+        result.draw_stats_pending.sprites += 1;
+        result.state = State::WaitingForVblank;
 
         assert_eq!(target, result);
         Ok(())
@@ -1001,11 +1534,12 @@ mod tests {
         #[with(target.clone())] mut result: Chip8,
         #[values(1, 2)] vx: usize,
     ) -> Result<()> {
-        target.memory.i = 0x1000;
+        let i = (target.ram_size() - 1) as u16;
+        target.memory.i = i;
 
         target.execute(&Instruction::AddIWithVx { vx })?;
 
-        result.memory.i = 0x1000 + result.memory.v[vx] as u16;
+        result.memory.i = i.wrapping_add(result.memory.v[vx] as u16);
         result.memory.v[Memory::INDEX_FLAG_REGISTER] = 1;
 
         assert_eq!(target, result);
@@ -1044,6 +1578,65 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    #[case(IndexOverflow::Full, 0xFFF, 0x1001)]
+    #[case(IndexOverflow::Full, 0xFFFF, 0x0001)]
+    #[case(IndexOverflow::Wrap, 0xFFF, 0x001)]
+    #[case(IndexOverflow::Wrap, 0xFFFF, 0x001)]
+    #[case(IndexOverflow::Saturate, 0xFFF, 0xFFF)]
+    #[case(IndexOverflow::Saturate, 0xFFFF, 0xFFF)]
+    fn execute_add_i_with_vx_compat_index_overflow(
+        #[case] index_overflow: IndexOverflow,
+        #[case] i: u16,
+        #[case] result_i: u16,
+    ) -> Result<()> {
+        let config = Config {
+            index_overflow,
+            add_to_index_stores_overflow: true,
+            ..Config::default()
+        };
+        let mut target = target(config.clone());
+        let mut result = target.clone();
+        target.memory.i = i;
+
+        // V2 holds 2
+        target.execute(&Instruction::AddIWithVx { vx: 2 })?;
+
+        result.memory.i = result_i;
+        result.memory.v[Memory::INDEX_FLAG_REGISTER] = 1;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0x300, 0x2FD, false)]
+    #[case(0x300, 0x2FE, true)]
+    #[case(0x10000, 0xFFF, false)]
+    #[case(0x10000, 0xFFFE, true)]
+    fn execute_add_i_with_vx_overflow_follows_ram_size(
+        #[case] ram_size: usize,
+        #[case] i: u16,
+        #[case] overflow: bool,
+    ) -> Result<()> {
+        let mut target = Chip8::new(Config {
+            add_to_index_stores_overflow: true,
+            ..Config::default()
+        })
+        .with_ram_size(ram_size)?;
+        target.memory.i = i;
+        target.memory.v[0x2] = 2;
+
+        target.execute(&Instruction::AddIWithVx { vx: 2 })?;
+
+        assert_eq!(
+            target.memory.v[Memory::INDEX_FLAG_REGISTER],
+            overflow.into()
+        );
+        Ok(())
+    }
+
     #[rstest]
     fn execute_set_i_with_character_at_vx(
         mut target: Chip8,
@@ -1058,6 +1651,54 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn execute_set_i_with_large_character_at_vx(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0, 1, 2, 3, 4, 5, 6, 7, 8, 9)] digit: u8,
+    ) -> Result<()> {
+        target.memory.v[3] = digit;
+        target.execute(&Instruction::SetIWithLargeCharacterAtVx { vx: 3 })?;
+
+        result.memory.v[3] = digit;
+        result.memory.i = 0xA0 + digit as u16 * 10;
+
+        assert_eq!(target, result);
+        assert_eq!(
+            &target.memory.ram[target.memory.i as usize..][..10],
+            &Memory::FONT_LARGE[digit as usize]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_set_audio_pattern_with_i(mut target: Chip8, mut result: Chip8) -> Result<()> {
+        let pattern: [u8; 16] = core::array::from_fn(|i| i as u8 * 0x11);
+        target.memory.ram[target.memory.i as usize..][..16].copy_from_slice(&pattern);
+
+        target.execute(&Instruction::SetAudioPatternWithI)?;
+
+        result.memory.ram[result.memory.i as usize..][..16].copy_from_slice(&pattern);
+        result.memory.audio_pattern = pattern;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn execute_set_pitch_with_vx(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0, 7, 15)] vx: usize,
+    ) -> Result<()> {
+        target.execute(&Instruction::SetPitchWithVx { vx })?;
+
+        result.memory.pitch = result.memory.v[vx];
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
     #[rstest]
     fn execute_set_i_with_binary_to_decimal_at_vx(
         mut target: Chip8,
@@ -1069,9 +1710,10 @@ mod tests {
         target.execute(&Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx })?;
 
         result.memory.v[vx] = value;
-        result.memory.ram[result.memory.i as usize + 0] = (value / 100) % 10;
+        result.memory.ram[result.memory.i as usize] = (value / 100) % 10;
         result.memory.ram[result.memory.i as usize + 1] = (value / 10) % 10;
-        result.memory.ram[result.memory.i as usize + 2] = (value / 1) % 10;
+        result.memory.ram[result.memory.i as usize + 2] = value % 10;
+        result.memory.written = (0..3).map(|offset| result.memory.i + offset).collect();
 
         assert_eq!(target, result);
         Ok(())
@@ -1088,6 +1730,7 @@ mod tests {
         for i in 0..=vx {
             result.memory.ram[result.memory.i as usize + i] = result.memory.v[i];
         }
+        result.memory.written = (0..=vx as u16).map(|i| result.memory.i + i).collect();
 
         assert_eq!(target, result);
         Ok(())
@@ -1104,6 +1747,7 @@ mod tests {
         for i in 0..=vx {
             result.memory.ram[result.memory.i as usize + i] = result.memory.v[i];
         }
+        result.memory.written = (0..=vx as u16).map(|i| result.memory.i + i).collect();
         result.memory.i += vx as u16 + 1;
 
         assert_eq!(target, result);
@@ -1154,4 +1798,65 @@ mod tests {
         assert_eq!(target, result);
         Ok(())
     }
+
+    #[rstest]
+    fn execute_set_i_with_binary_to_decimal_at_vx_wraps_i(
+        mut target: Chip8,
+        mut result: Chip8,
+        #[values(0xFFE, 0xFFF)] i: u16,
+    ) -> Result<()> {
+        target.memory.i = i;
+        target.memory.v[0] = 156;
+        target.execute(&Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx: 0 })?;
+
+        result.memory.i = i;
+        result.memory.v[0] = 156;
+        for (offset, digit) in [1, 5, 6].into_iter().enumerate() {
+            result.memory.ram[(i as usize + offset) % Memory::SIZE_RAM] = digit;
+        }
+        result.memory.written = (0..3)
+            .map(|offset| ((i as usize + offset) % Memory::SIZE_RAM) as u16)
+            .collect();
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn execute_store_registers_until_wraps_i(
+        #[with(Config { store_load_modifies_i: true, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.i = 0xFFE;
+        target.execute(&Instruction::StoreRegistersUntil { vx: 4 })?;
+
+        result.memory.ram[0xFFE..].copy_from_slice(&result.memory.v[..2]);
+        result.memory.ram[..3].copy_from_slice(&result.memory.v[2..5]);
+        result.memory.i = 0xFFE + 5;
+        result.memory.written = vec![0xFFE, 0xFFF, 0x000, 0x001, 0x002];
+
+        assert_eq!(target, result);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "xo-chip"))]
+    #[rstest]
+    fn execute_load_registers_until_wraps_i(
+        #[with(Config { store_load_modifies_i: true, ..Config::default() })] mut target: Chip8,
+        #[with(target.clone())] mut result: Chip8,
+    ) -> Result<()> {
+        target.memory.i = 0xFFE;
+        target.memory.ram[0xFFE..].copy_from_slice(&[20, 30]);
+        target.memory.ram[..3].copy_from_slice(&[40, 50, 60]);
+        target.execute(&Instruction::LoadRegistersUntil { vx: 4 })?;
+
+        result.memory.ram[0xFFE..].copy_from_slice(&[20, 30]);
+        result.memory.ram[..3].copy_from_slice(&[40, 50, 60]);
+        result.memory.v[..5].copy_from_slice(&[20, 30, 40, 50, 60]);
+        result.memory.i = 0xFFE + 5;
+
+        assert_eq!(target, result);
+        Ok(())
+    }
 }
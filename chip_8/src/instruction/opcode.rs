@@ -1,3 +1,5 @@
+use core::fmt;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 /// Instruction bytes split into multiple parts.
 pub struct Opcode {
@@ -42,6 +44,60 @@ impl From<(u8, u8)> for Opcode {
     }
 }
 
+impl Opcode {
+    /// First nibble, usually the kind of the instruction.
+    pub fn i(&self) -> usize {
+        self.i
+    }
+
+    /// Second nibble, usually the first register.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// Third nibble, usually the second register.
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// Forth nibble.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Last byte, usually a value.
+    pub fn nn(&self) -> u8 {
+        self.nn
+    }
+
+    /// Last 12-bit word, usually an address.
+    pub fn nnn(&self) -> u16 {
+        self.nnn
+    }
+
+    /// Whether the opcode is followed by a 16-bit operand word, which is only the case for `F000 NNNN`.
+    pub fn has_operand(&self) -> bool {
+        self.raw() == 0xF000
+    }
+
+    /// Instruction bytes the opcode was made from.
+    pub fn raw(&self) -> u16 {
+        ((self.i as u16) << 12) | self.nnn
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:04X}", self.raw())
+    }
+}
+
+impl fmt::LowerHex for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.raw(), f)
+    }
+}
+
 impl From<Opcode> for (usize, usize, usize, usize, u8, u16) {
     fn from(value: Opcode) -> Self {
         (value.i, value.x, value.y, value.n, value.nn, value.nnn)
@@ -124,4 +180,59 @@ mod tests {
 
         Ok(())
     }
+
+    #[rstest]
+    fn accessors_match_tuple(
+        #[values(0x00E0, 0xD123, 0xA974, 0x8AB6, 0xFF65)] opcode: u16,
+    ) -> Result<()> {
+        let opcode = Opcode::from(opcode);
+
+        assert_eq!(
+            (
+                opcode.i(),
+                opcode.x(),
+                opcode.y(),
+                opcode.n(),
+                opcode.nn(),
+                opcode.nnn()
+            ),
+            opcode.into()
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn has_operand_only_for_f000() -> Result<()> {
+        assert!(Opcode::from(0xF000).has_operand());
+        assert!(!Opcode::from(0xF001).has_operand());
+        assert!(!Opcode::from(0xF100).has_operand());
+        Ok(())
+    }
+
+    #[rstest]
+    fn raw_round_trips() -> Result<()> {
+        for opcode in 0..=u16::MAX {
+            assert_eq!(Opcode::from(opcode).raw(), opcode);
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    fn display_formats_word(
+        #[values((0x8A24, "0x8A24"), (0x00E0, "0x00E0"), (0x0005, "0x0005"))] opcode: (u16, &str),
+    ) -> Result<()> {
+        let (opcode, result) = opcode;
+
+        assert_eq!(Opcode::from(opcode).to_string(), result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn lower_hex_formats_word() -> Result<()> {
+        assert_eq!(format!("{:x}", Opcode::from(0x8A24)), "8a24");
+        assert_eq!(format!("{:x}", Opcode::from(0x00E0)), "e0");
+        assert_eq!(format!("{:04x}", Opcode::from(0x00E0)), "00e0");
+        assert_eq!(format!("{:#06x}", Opcode::from(0x00E0)), "0x00e0");
+        Ok(())
+    }
 }
@@ -1,11 +1,18 @@
 use super::*;
+#[cfg(feature = "std")]
 use thiserror::Error;
 
 /// Errors encountered during parsing of an opcode.
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
 pub enum ParseError {
-    #[error("opcode {0:?} is unknown")]
+    #[cfg_attr(feature = "std", error("opcode {0} is unknown"))]
     UnknownOpcode(Opcode),
+    #[cfg_attr(
+        feature = "std",
+        error("opcode {0} needs the operand word that follows it")
+    )]
+    MissingOperand(Opcode),
 }
 
 /// CPU instruction with required arguments.
@@ -21,6 +28,11 @@ pub enum Instruction {
     /// * Opcode: `00EE`
     /// * Mnemonic: `RET`
     SubroutineReturn,
+    /// Stop the interpreter, see [`crate::Chip8::is_halted`].
+    ///
+    /// * Opcode: `00FD`
+    /// * Mnemonic: `EXIT`
+    Exit,
     /// Execute machine code routine at address.
     /// **WARNING:** Is unsupported.
     ///
@@ -126,9 +138,18 @@ pub enum Instruction {
     /// * Opcode: `Annn`
     /// * Mnemonic: `LD I addr`
     SetIWithValue { value: u16 },
+    /// Load a 16-bit address stored in the word after the opcode into `I`.
+    ///
+    /// **XO-CHIP:** Not available on the original hardware, takes up 4 bytes.
+    ///
+    /// * Opcode: `F000 NNNN`
+    /// * Mnemonic: `LD I long`
+    SetIWithLongValue { value: u16 },
     /// Jump to the offset + value in `V0`.
     ///
     /// **COMPATIBILITY:** Optionally use `Vx` instead of `V0`.
+    /// Then the opcode is read as `Bxnn`, `address` still holds the whole `xnn`
+    /// and `vx` only picks the register, so the jump goes to `xnn + Vx`.
     ///
     /// * Opcode: `Bnnn`
     /// * Mnemonic: `JP V0 + addr` or `JP Vx + addr`
@@ -140,6 +161,8 @@ pub enum Instruction {
     SetVxWithRandom { vx: usize, value: u8 },
     /// Display a sprite from `I` with specified height in the coordinates from `Vx` and `Vy`.
     ///
+    /// `height` is a 4-bit field, instructions built with a height above 15 fail to execute.
+    ///
     /// * Opcode: `Dxyn`
     /// * Mnemonic: `DRW Vx Vy height`
     DisplayDraw { vx: usize, vy: usize, height: u8 },
@@ -160,6 +183,7 @@ pub enum Instruction {
     SetVxWithDt { vx: usize },
     /// Stop execution and wait until a key is pressed.
     /// A key that was pressed is stored in `Vx`.
+    /// A key tapped earlier in the same frame is stored right away instead of waiting.
     ///
     /// * Opcode: `Fx0A`
     /// * Mnemonic: `LD Vx key`
@@ -186,6 +210,27 @@ pub enum Instruction {
     /// * Opcode: `Fx29`
     /// * Mnemonic: `LD F Vx`
     SetIWithCharacterAtVx { vx: usize },
+    /// Set `I` to large font character in `Vx`.
+    ///
+    /// **SUPER-CHIP:** Not available on the original hardware.
+    ///
+    /// * Opcode: `Fx30`
+    /// * Mnemonic: `LD HF Vx`
+    SetIWithLargeCharacterAtVx { vx: usize },
+    /// Load the audio pattern from 16 bytes in RAM at `I`.
+    ///
+    /// **XO-CHIP:** Not available on the original hardware.
+    ///
+    /// * Opcode: `F002`
+    /// * Mnemonic: `LD AUDIO [I]`
+    SetAudioPatternWithI,
+    /// Load a value from `Vx` into the pitch register.
+    ///
+    /// **XO-CHIP:** Not available on the original hardware.
+    ///
+    /// * Opcode: `Fx3A`
+    /// * Mnemonic: `LD PITCH Vx`
+    SetPitchWithVx { vx: usize },
     /// Set in RAM at `I`, `I + 1`, and `I + 2` to individual digits of a value at `Vx`.
     ///
     /// * Opcode: `Fx33`
@@ -207,15 +252,200 @@ pub enum Instruction {
     LoadRegistersUntil { vx: usize },
 }
 
+impl Instruction {
+    /// Indexes of the registers the instruction accesses as `[Vx, Vy]`.
+    ///
+    /// `V0` implicitly used by `Bnnn` is not included.
+    pub fn registers(&self) -> [Option<usize>; 2] {
+        match *self {
+            Instruction::SkipIfVxEqualsVy { vx, vy }
+            | Instruction::SetVxWithVy { vx, vy }
+            | Instruction::OrVxWithVy { vx, vy }
+            | Instruction::AndVxWithVy { vx, vy }
+            | Instruction::XorVxWithVy { vx, vy }
+            | Instruction::AddVxWithVy { vx, vy }
+            | Instruction::SubtractVxWithVy { vx, vy }
+            | Instruction::Shift1RightVxWithVy { vx, vy }
+            | Instruction::SubtractVyWithVx { vx, vy }
+            | Instruction::Shift1LeftVxWithVy { vx, vy }
+            | Instruction::SkipIfVxNotEqualsVy { vx, vy }
+            | Instruction::DisplayDraw { vx, vy, .. } => [Some(vx), Some(vy)],
+            Instruction::SkipIfVxEqualsValue { vx, .. }
+            | Instruction::SkipIfVxNotEqualsValue { vx, .. }
+            | Instruction::SetVxWithValue { vx, .. }
+            | Instruction::AddVxValue { vx, .. }
+            | Instruction::JumpWithOffset { vx, .. }
+            | Instruction::SetVxWithRandom { vx, .. }
+            | Instruction::SkipIfVxKeyPressed { vx }
+            | Instruction::SkipIfVxKeyNotPressed { vx }
+            | Instruction::SetVxWithDt { vx }
+            | Instruction::SetVxWithNextPressedKeyBlocking { vx }
+            | Instruction::SetDtWithVx { vx }
+            | Instruction::SetStWithVx { vx }
+            | Instruction::AddIWithVx { vx }
+            | Instruction::SetIWithCharacterAtVx { vx }
+            | Instruction::SetIWithLargeCharacterAtVx { vx }
+            | Instruction::SetPitchWithVx { vx }
+            | Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx }
+            | Instruction::StoreRegistersUntil { vx }
+            | Instruction::LoadRegistersUntil { vx } => [Some(vx), None],
+            Instruction::DisplayClear
+            | Instruction::SubroutineReturn
+            | Instruction::Exit
+            | Instruction::System { .. }
+            | Instruction::Jump { .. }
+            | Instruction::SubroutineCall { .. }
+            | Instruction::SetIWithValue { .. }
+            | Instruction::SetIWithLongValue { .. }
+            | Instruction::SetAudioPatternWithI => [None, None],
+        }
+    }
+
+    /// Approximate cost of the instruction in machine cycles of the COSMAC VIP interpreter,
+    /// including fetching and decoding it.
+    ///
+    /// The real costs depend on the operands and on where the interpreter is in its loop,
+    /// so these are averages. Instructions that did not exist on the VIP are priced like their closest relative.
+    pub fn cycles(&self) -> u32 {
+        /// Cost of fetching and decoding an opcode.
+        const FETCH: u32 = 10;
+        /// Cost of drawing each row of a sprite.
+        const DRAW_ROW: u32 = 68;
+        /// Cost of copying each register to or from RAM.
+        const COPY_REGISTER: u32 = 14;
+
+        FETCH
+            + match *self {
+                Instruction::DisplayClear => 668,
+                Instruction::SubroutineReturn
+                | Instruction::Exit
+                | Instruction::System { .. }
+                | Instruction::Jump { .. }
+                | Instruction::SubroutineCall { .. }
+                | Instruction::JumpWithOffset { .. } => 13,
+                Instruction::SkipIfVxEqualsValue { .. }
+                | Instruction::SkipIfVxNotEqualsValue { .. }
+                | Instruction::SkipIfVxEqualsVy { .. }
+                | Instruction::SkipIfVxNotEqualsVy { .. }
+                | Instruction::SkipIfVxKeyPressed { .. }
+                | Instruction::SkipIfVxKeyNotPressed { .. } => 4,
+                Instruction::SetVxWithValue { .. }
+                | Instruction::SetVxWithDt { .. }
+                | Instruction::SetVxWithNextPressedKeyBlocking { .. }
+                | Instruction::SetDtWithVx { .. }
+                | Instruction::SetStWithVx { .. }
+                | Instruction::SetPitchWithVx { .. } => 0,
+                Instruction::SetIWithValue { .. } | Instruction::AddVxValue { .. } => 2,
+                Instruction::SetIWithLongValue { .. } => 12,
+                Instruction::SetVxWithVy { .. }
+                | Instruction::OrVxWithVy { .. }
+                | Instruction::AndVxWithVy { .. }
+                | Instruction::XorVxWithVy { .. }
+                | Instruction::AddVxWithVy { .. }
+                | Instruction::SubtractVxWithVy { .. }
+                | Instruction::Shift1RightVxWithVy { .. }
+                | Instruction::SubtractVyWithVx { .. }
+                | Instruction::Shift1LeftVxWithVy { .. } => 34,
+                Instruction::SetVxWithRandom { .. } => 26,
+                Instruction::DisplayDraw { height, .. } => 160 + DRAW_ROW * height as u32,
+                Instruction::AddIWithVx { .. } => 9,
+                Instruction::SetIWithCharacterAtVx { .. }
+                | Instruction::SetIWithLargeCharacterAtVx { .. } => 10,
+                Instruction::SetAudioPatternWithI => 16 * COPY_REGISTER,
+                Instruction::SetRamAtIWithBinaryToDecimalAtVx { .. } => 194,
+                Instruction::StoreRegistersUntil { vx }
+                | Instruction::LoadRegistersUntil { vx } => (vx as u32 + 1) * COPY_REGISTER,
+            }
+    }
+
+    /// How many bytes of RAM the instruction takes up.
+    pub fn size(&self) -> u16 {
+        match self {
+            Instruction::SetIWithLongValue { .. } => 4,
+            _ => 2,
+        }
+    }
+
+    /// Opcode that the instruction is decoded from, with its operands as letters like `8xy4`.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Instruction::DisplayClear => "00E0",
+            Instruction::SubroutineReturn => "00EE",
+            Instruction::Exit => "00FD",
+            Instruction::System { .. } => "0nnn",
+            Instruction::Jump { .. } => "1nnn",
+            Instruction::SubroutineCall { .. } => "2nnn",
+            Instruction::SkipIfVxEqualsValue { .. } => "3xnn",
+            Instruction::SkipIfVxNotEqualsValue { .. } => "4xnn",
+            Instruction::SkipIfVxEqualsVy { .. } => "5xy0",
+            Instruction::SetVxWithValue { .. } => "6xnn",
+            Instruction::AddVxValue { .. } => "7xnn",
+            Instruction::SetVxWithVy { .. } => "8xy0",
+            Instruction::OrVxWithVy { .. } => "8xy1",
+            Instruction::AndVxWithVy { .. } => "8xy2",
+            Instruction::XorVxWithVy { .. } => "8xy3",
+            Instruction::AddVxWithVy { .. } => "8xy4",
+            Instruction::SubtractVxWithVy { .. } => "8xy5",
+            Instruction::Shift1RightVxWithVy { .. } => "8xy6",
+            Instruction::SubtractVyWithVx { .. } => "8xy7",
+            Instruction::Shift1LeftVxWithVy { .. } => "8xyE",
+            Instruction::SkipIfVxNotEqualsVy { .. } => "9xy0",
+            Instruction::SetIWithValue { .. } => "Annn",
+            Instruction::SetIWithLongValue { .. } => "F000",
+            Instruction::JumpWithOffset { .. } => "Bnnn",
+            Instruction::SetVxWithRandom { .. } => "Cxnn",
+            Instruction::DisplayDraw { .. } => "Dxyn",
+            Instruction::SkipIfVxKeyPressed { .. } => "Ex9E",
+            Instruction::SkipIfVxKeyNotPressed { .. } => "ExA1",
+            Instruction::SetAudioPatternWithI => "F002",
+            Instruction::SetVxWithDt { .. } => "Fx07",
+            Instruction::SetVxWithNextPressedKeyBlocking { .. } => "Fx0A",
+            Instruction::SetDtWithVx { .. } => "Fx15",
+            Instruction::SetStWithVx { .. } => "Fx18",
+            Instruction::AddIWithVx { .. } => "Fx1E",
+            Instruction::SetIWithCharacterAtVx { .. } => "Fx29",
+            Instruction::SetIWithLargeCharacterAtVx { .. } => "Fx30",
+            Instruction::SetRamAtIWithBinaryToDecimalAtVx { .. } => "Fx33",
+            Instruction::SetPitchWithVx { .. } => "Fx3A",
+            Instruction::StoreRegistersUntil { .. } => "Fx55",
+            Instruction::LoadRegistersUntil { .. } => "Fx65",
+        }
+    }
+
+    /// Parse an opcode that may need the word after it, see [`Opcode::has_operand`].
+    ///
+    /// # Arguments
+    ///
+    /// * `opcode` - Opcode to parse.
+    /// * `operand` - Word stored after the opcode, ignored by single-word instructions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the opcode is unknown.
+    pub fn decode(opcode: Opcode, operand: u16) -> Result<Self, ParseError> {
+        if opcode.has_operand() {
+            Ok(Instruction::SetIWithLongValue { value: operand })
+        } else {
+            Self::try_from(opcode)
+        }
+    }
+}
+
 impl TryFrom<Opcode> for Instruction {
     type Error = ParseError;
 
     fn try_from(value: Opcode) -> Result<Self, Self::Error> {
-        let (i, x, y, n, nn, nnn) = value.into();
+        let (x, y, n) = (value.x(), value.y(), value.n());
+        let (nn, nnn) = (value.nn(), value.nnn());
 
-        let instruction = match (i, x, y, n) {
+        if value.has_operand() {
+            return Err(ParseError::MissingOperand(value));
+        }
+
+        let instruction = match (value.i(), x, y, n) {
             (0x0, 0x0, 0xE, 0x0) => Instruction::DisplayClear,
             (0x0, 0x0, 0xE, 0xE) => Instruction::SubroutineReturn,
+            (0x0, 0x0, 0xF, 0xD) => Instruction::Exit,
             (0x0, _, _, _) => Instruction::System { address: nnn },
             (0x1, _, _, _) => Instruction::Jump { address: nnn },
             (0x2, _, _, _) => Instruction::SubroutineCall { address: nnn },
@@ -247,12 +477,15 @@ impl TryFrom<Opcode> for Instruction {
             },
             (0xE, _, 0x9, 0xE) => Instruction::SkipIfVxKeyPressed { vx: x },
             (0xE, _, 0xA, 0x1) => Instruction::SkipIfVxKeyNotPressed { vx: x },
+            (0xF, 0x0, 0x0, 0x2) => Instruction::SetAudioPatternWithI,
             (0xF, _, 0x0, 0x7) => Instruction::SetVxWithDt { vx: x },
             (0xF, _, 0x0, 0xA) => Instruction::SetVxWithNextPressedKeyBlocking { vx: x },
             (0xF, _, 0x1, 0x5) => Instruction::SetDtWithVx { vx: x },
             (0xF, _, 0x1, 0x8) => Instruction::SetStWithVx { vx: x },
             (0xF, _, 0x1, 0xE) => Instruction::AddIWithVx { vx: x },
             (0xF, _, 0x2, 0x9) => Instruction::SetIWithCharacterAtVx { vx: x },
+            (0xF, _, 0x3, 0x0) => Instruction::SetIWithLargeCharacterAtVx { vx: x },
+            (0xF, _, 0x3, 0xA) => Instruction::SetPitchWithVx { vx: x },
             (0xF, _, 0x3, 0x3) => Instruction::SetRamAtIWithBinaryToDecimalAtVx { vx: x },
             (0xF, _, 0x5, 0x5) => Instruction::StoreRegistersUntil { vx: x },
             (0xF, _, 0x6, 0x5) => Instruction::LoadRegistersUntil { vx: x },
@@ -283,6 +516,26 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn pattern_matches_opcode(
+        #[values(
+            0x00E0, 0x00EE, 0x00FD, 0x0123, 0x8AB4, 0x8ABE, 0xD125, 0xF000, 0xF30A, 0xFA65
+        )]
+        opcode: u16,
+    ) -> Result<()> {
+        let instruction = Instruction::decode(Opcode::from(opcode), 0)?;
+        let pattern = instruction.pattern();
+
+        assert_eq!(pattern.len(), 4);
+        for (digit, letter) in format!("{opcode:04X}").chars().zip(pattern.chars()) {
+            assert!(
+                letter.is_lowercase() || letter == digit,
+                "{pattern} for {opcode:04X}"
+            );
+        }
+        Ok(())
+    }
+
     #[rstest]
     fn from_opcode_00e0_returns_display_clear() -> Result<()> {
         assert_eq!(
@@ -301,6 +554,15 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn from_opcode_00fd_returns_exit() -> Result<()> {
+        assert_eq!(
+            Instruction::try_from(Opcode::from(0x00FD)),
+            Ok(Instruction::Exit)
+        );
+        Ok(())
+    }
+
     #[rstest]
     fn from_opcode_0nnn_returns_system(#[values(0x123, 0x234)] address: u16) -> Result<()> {
         assert_eq!(
@@ -637,6 +899,17 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn from_opcode_fx30_returns_set_i_with_large_character_at_vx(
+        #[values(1, 2)] vx: usize,
+    ) -> Result<()> {
+        assert_eq!(
+            Instruction::try_from(Opcode::from(opcode! { i: 0xF, x: vx, nn: 0x30 })),
+            Ok(Instruction::SetIWithLargeCharacterAtVx { vx })
+        );
+        Ok(())
+    }
+
     #[rstest]
     fn from_opcode_fx33_returns_set_ram_at_i_with_binary_to_decimal_at_vx(
         #[values(1, 2)] vx: usize,
@@ -665,4 +938,171 @@ mod tests {
         );
         Ok(())
     }
+
+    #[rstest]
+    fn from_opcode_never_returns_invalid_register() -> Result<()> {
+        for opcode in 0..=u16::MAX {
+            if let Ok(instruction) = Instruction::try_from(Opcode::from(opcode)) {
+                assert!(
+                    instruction
+                        .registers()
+                        .into_iter()
+                        .flatten()
+                        .all(|r| r <= 0xF),
+                    "{opcode:04X} decoded into {instruction:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    fn registers_returns_vx_and_vy() -> Result<()> {
+        assert_eq!(
+            Instruction::DisplayDraw {
+                vx: 0x1,
+                vy: 0x2,
+                height: 3
+            }
+            .registers(),
+            [Some(0x1), Some(0x2)]
+        );
+        assert_eq!(
+            Instruction::AddIWithVx { vx: 0xF }.registers(),
+            [Some(0xF), None]
+        );
+        assert_eq!(
+            Instruction::Jump { address: 0x200 }.registers(),
+            [None, None]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_opcode_f002_returns_set_audio_pattern_with_i() -> Result<()> {
+        assert_eq!(
+            Instruction::try_from(Opcode::from(0xF002)),
+            Ok(Instruction::SetAudioPatternWithI)
+        );
+        assert_eq!(
+            Instruction::try_from(Opcode::from(0xF102)),
+            Err(ParseError::UnknownOpcode(Opcode::from(0xF102)))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_opcode_fx3a_returns_set_pitch_with_vx(#[values(1, 2)] vx: usize) -> Result<()> {
+        assert_eq!(
+            Instruction::try_from(Opcode::from(opcode! { i: 0xF, x: vx, nn: 0x3A })),
+            Ok(Instruction::SetPitchWithVx { vx })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_opcode_f000_needs_operand() -> Result<()> {
+        assert_eq!(
+            Instruction::try_from(Opcode::from(0xF000)),
+            Err(ParseError::MissingOperand(Opcode::from(0xF000)))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn decode_f000_nnnn_returns_set_i_with_long_value(
+        #[values(0x0000, 0x1234, 0xFFFF)] value: u16,
+    ) -> Result<()> {
+        let instruction = Instruction::decode(Opcode::from(0xF000), value)?;
+
+        assert_eq!(instruction, Instruction::SetIWithLongValue { value });
+        assert_eq!(instruction.size(), 4);
+        Ok(())
+    }
+
+    #[rstest]
+    fn decode_ignores_operand_of_single_word_opcode() -> Result<()> {
+        let instruction = Instruction::decode(Opcode::from(0xA123), 0xFFFF)?;
+
+        assert_eq!(instruction, Instruction::SetIWithValue { value: 0x123 });
+        assert_eq!(instruction.size(), 2);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(0x00E0, 678)]
+    #[case(0x00EE, 23)]
+    #[case(0x1200, 23)]
+    #[case(0x2200, 23)]
+    #[case(0xB200, 23)]
+    #[case(0x3012, 14)]
+    #[case(0x4012, 14)]
+    #[case(0x5120, 14)]
+    #[case(0x9120, 14)]
+    #[case(0xE19E, 14)]
+    #[case(0xE1A1, 14)]
+    #[case(0x6012, 10)]
+    #[case(0xF107, 10)]
+    #[case(0xF10A, 10)]
+    #[case(0xF115, 10)]
+    #[case(0xF118, 10)]
+    #[case(0xF13A, 10)]
+    #[case(0x7012, 12)]
+    #[case(0xA123, 12)]
+    #[case(0x8120, 44)]
+    #[case(0x8124, 44)]
+    #[case(0x812E, 44)]
+    #[case(0xC0FF, 36)]
+    #[case(0xF11E, 19)]
+    #[case(0xF129, 20)]
+    #[case(0xF130, 20)]
+    #[case(0xF133, 204)]
+    #[case(0xF002, 234)]
+    fn cycles_of_single_word_instruction(#[case] opcode: u16, #[case] cycles: u32) -> Result<()> {
+        let instruction = Instruction::try_from(Opcode::from(opcode))?;
+
+        assert_eq!(instruction.cycles(), cycles, "{instruction:?}");
+        Ok(())
+    }
+
+    #[rstest]
+    fn cycles_of_set_i_with_long_value() -> Result<()> {
+        assert_eq!(
+            Instruction::SetIWithLongValue { value: 0x1234 }.cycles(),
+            22
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn cycles_of_display_draw_grow_with_height(#[values(0, 1, 5, 15)] height: u8) -> Result<()> {
+        assert_eq!(
+            Instruction::DisplayDraw {
+                vx: 0,
+                vy: 1,
+                height
+            }
+            .cycles(),
+            170 + 68 * height as u32
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn cycles_of_register_copies_grow_with_count(#[values(0x0, 0x7, 0xF)] vx: usize) -> Result<()> {
+        let cycles = 10 + 14 * (vx as u32 + 1);
+
+        assert_eq!(Instruction::StoreRegistersUntil { vx }.cycles(), cycles);
+        assert_eq!(Instruction::LoadRegistersUntil { vx }.cycles(), cycles);
+        Ok(())
+    }
+
+    #[rstest]
+    fn unknown_opcode_message() -> Result<()> {
+        assert_eq!(
+            ParseError::UnknownOpcode(Opcode::from(0x5001)).to_string(),
+            "opcode 0x5001 is unknown"
+        );
+        Ok(())
+    }
 }
@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![feature(slice_flatten)]
 #![warn(clippy::pedantic)]
 #![allow(
@@ -13,6 +14,10 @@
     clippy::wildcard_imports
 )]
 #![warn(unused_imports)]
+// Tests return `Result` to use `?`, even when nothing in them can fail.
+#![cfg_attr(test, allow(clippy::unnecessary_wraps))]
+
+extern crate alloc;
 
 mod chip_8;
 mod instruction;
@@ -0,0 +1,206 @@
+use chip_8::{Chip8, Config, Memory};
+use eyre::Result;
+use rstest::*;
+use similar_asserts::assert_eq;
+
+/// Checks the arithmetic, logic, comparison, call and memory opcodes, counting the tests in register E.
+/// Draws `OK` in the middle of the screen if all of them pass, or the number of the failed test otherwise.
+const ROM: &[u8] = &[
+    0x6E, 0x00, // 0x200: Count the tests in register E
+    0x7E, 0x01, // 0x202: Test 1: 7xnn adds
+    0x60, 0x05, // 0x204: Load 5 into register 0
+    0x70, 0x03, // 0x206: Add 3 to register 0
+    0x30, 0x08, // 0x208: Skip if register 0 is 8
+    0x12, 0xF8, // 0x20A: Fail
+    0x7E, 0x01, // 0x20C: Test 2: 8xy0 copies
+    0x61, 0x12, // 0x20E: Load 0x12 into register 1
+    0x82, 0x10, // 0x210: Copy register 1 to register 2
+    0x32, 0x12, // 0x212: Skip if register 2 is 0x12
+    0x12, 0xF8, // 0x214: Fail
+    0x7E, 0x01, // 0x216: Test 3: 8xy1 ors
+    0x60, 0xF0, // 0x218: Load 0xF0 into register 0
+    0x61, 0x0F, // 0x21A: Load 0x0F into register 1
+    0x80, 0x11, // 0x21C: Or register 1 into register 0
+    0x30, 0xFF, // 0x21E: Skip if register 0 is 0xFF
+    0x12, 0xF8, // 0x220: Fail
+    0x7E, 0x01, // 0x222: Test 4: 8xy2 ands
+    0x60, 0xF0, // 0x224: Load 0xF0 into register 0
+    0x61, 0x3C, // 0x226: Load 0x3C into register 1
+    0x80, 0x12, // 0x228: And register 1 into register 0
+    0x30, 0x30, // 0x22A: Skip if register 0 is 0x30
+    0x12, 0xF8, // 0x22C: Fail
+    0x7E, 0x01, // 0x22E: Test 5: 8xy3 xors
+    0x60, 0xFF, // 0x230: Load 0xFF into register 0
+    0x61, 0x0F, // 0x232: Load 0x0F into register 1
+    0x80, 0x13, // 0x234: Xor register 1 into register 0
+    0x30, 0xF0, // 0x236: Skip if register 0 is 0xF0
+    0x12, 0xF8, // 0x238: Fail
+    0x7E, 0x01, // 0x23A: Test 6: 8xy4 adds with carry
+    0x60, 0xFF, // 0x23C: Load 0xFF into register 0
+    0x61, 0x02, // 0x23E: Load 0x02 into register 1
+    0x80, 0x14, // 0x240: Add register 1 to register 0
+    0x30, 0x01, // 0x242: Skip if register 0 is 0x01
+    0x12, 0xF8, // 0x244: Fail
+    0x3F, 0x01, // 0x246: Skip if the flag is 1
+    0x12, 0xF8, // 0x248: Fail
+    0x7E, 0x01, // 0x24A: Test 7: 8xy5 subtracts with borrow
+    0x60, 0x01, // 0x24C: Load 0x01 into register 0
+    0x61, 0x02, // 0x24E: Load 0x02 into register 1
+    0x80, 0x15, // 0x250: Subtract register 1 from register 0
+    0x30, 0xFF, // 0x252: Skip if register 0 is 0xFF
+    0x12, 0xF8, // 0x254: Fail
+    0x3F, 0x00, // 0x256: Skip if the flag is 0
+    0x12, 0xF8, // 0x258: Fail
+    0x7E, 0x01, // 0x25A: Test 8: 8xy7 subtracts reversed
+    0x60, 0x02, // 0x25C: Load 0x02 into register 0
+    0x61, 0x05, // 0x25E: Load 0x05 into register 1
+    0x80, 0x17, // 0x260: Subtract register 0 from register 1 into register 0
+    0x30, 0x03, // 0x262: Skip if register 0 is 0x03
+    0x12, 0xF8, // 0x264: Fail
+    0x3F, 0x01, // 0x266: Skip if the flag is 1
+    0x12, 0xF8, // 0x268: Fail
+    0x7E, 0x01, // 0x26A: Test 9: 8xy6 shifts right
+    0x60, 0x05, // 0x26C: Load 0x05 into register 0
+    0x61, 0x05, // 0x26E: Load 0x05 into register 1
+    0x80, 0x16, // 0x270: Shift register 0 right
+    0x30, 0x02, // 0x272: Skip if register 0 is 0x02
+    0x12, 0xF8, // 0x274: Fail
+    0x3F, 0x01, // 0x276: Skip if the flag is 1
+    0x12, 0xF8, // 0x278: Fail
+    0x7E, 0x01, // 0x27A: Test 10: 8xyE shifts left
+    0x60, 0x81, // 0x27C: Load 0x81 into register 0
+    0x61, 0x81, // 0x27E: Load 0x81 into register 1
+    0x80, 0x1E, // 0x280: Shift register 0 left
+    0x30, 0x02, // 0x282: Skip if register 0 is 0x02
+    0x12, 0xF8, // 0x284: Fail
+    0x3F, 0x01, // 0x286: Skip if the flag is 1
+    0x12, 0xF8, // 0x288: Fail
+    0x7E, 0x01, // 0x28A: Test 11: 4xnn, 5xy0 and 9xy0 compare
+    0x60, 0x07, // 0x28C: Load 7 into register 0
+    0x61, 0x07, // 0x28E: Load 7 into register 1
+    0x40, 0x08, // 0x290: Skip if register 0 is not 8
+    0x12, 0xF8, // 0x292: Fail
+    0x50, 0x10, // 0x294: Skip if registers 0 and 1 are equal
+    0x12, 0xF8, // 0x296: Fail
+    0x90, 0x10, // 0x298: Skip if registers 0 and 1 differ
+    0x12, 0x9E, // 0x29A: Continue
+    0x12, 0xF8, // 0x29C: Fail
+    0x7E, 0x01, // 0x29E: Test 12: 2nnn calls and 00EE returns
+    0x63, 0x00, // 0x2A0: Load 0 into register 3
+    0x23, 0x04, // 0x2A2: Call the subroutine
+    0x33, 0x42, // 0x2A4: Skip if register 3 is 0x42
+    0x12, 0xF8, // 0x2A6: Fail
+    0x7E, 0x01, // 0x2A8: Test 13: Fx1E adds to I, Fx33 stores BCD and Fx65 loads
+    0xA3, 0xFE, // 0x2AA: Point I at 0x3FE
+    0x60, 0x02, // 0x2AC: Load 2 into register 0
+    0xF0, 0x1E, // 0x2AE: Add register 0 to I
+    0x60, 0x7B, // 0x2B0: Load 123 into register 0
+    0xF0, 0x33, // 0x2B2: Store the digits of register 0 at I
+    0xF2, 0x65, // 0x2B4: Load registers 0 to 2 from I
+    0x30, 0x01, // 0x2B6: Skip if register 0 is 1
+    0x12, 0xF8, // 0x2B8: Fail
+    0x31, 0x02, // 0x2BA: Skip if register 1 is 2
+    0x12, 0xF8, // 0x2BC: Fail
+    0x32, 0x03, // 0x2BE: Skip if register 2 is 3
+    0x12, 0xF8, // 0x2C0: Fail
+    0x7E, 0x01, // 0x2C2: Test 14: Fx55 stores
+    0x60, 0x11, // 0x2C4: Load 0x11 into register 0
+    0x61, 0x22, // 0x2C6: Load 0x22 into register 1
+    0xA4, 0x10, // 0x2C8: Point I at 0x410
+    0xF1, 0x55, // 0x2CA: Store registers 0 and 1 at I
+    0x60, 0x00, // 0x2CC: Load 0 into register 0
+    0x61, 0x00, // 0x2CE: Load 0 into register 1
+    0xA4, 0x10, // 0x2D0: Point I at 0x410
+    0xF1, 0x65, // 0x2D2: Load registers 0 and 1 from I
+    0x30, 0x11, // 0x2D4: Skip if register 0 is 0x11
+    0x12, 0xF8, // 0x2D6: Fail
+    0x31, 0x22, // 0x2D8: Skip if register 1 is 0x22
+    0x12, 0xF8, // 0x2DA: Fail
+    0x7E, 0x01, // 0x2DC: Test 15: Cxnn masks
+    0x60, 0xFF, // 0x2DE: Load 0xFF into register 0
+    0xC0, 0x00, // 0x2E0: Load a random number masked to 0 into register 0
+    0x30, 0x00, // 0x2E2: Skip if register 0 is 0
+    0x12, 0xF8, // 0x2E4: Fail
+    0x00, 0xE0, // 0x2E6: Clear the display
+    0xA3, 0x08, // 0x2E8: Point I at the OK sprite
+    0x60, 0x1C, // 0x2EA: Load 28 into register 0
+    0x61, 0x0D, // 0x2EC: Load 13 into register 1
+    0xD0, 0x15, // 0x2EE: Draw O
+    0x70, 0x08, // 0x2F0: Add 8 to register 0
+    0xA3, 0x0D, // 0x2F2: Point I at K
+    0xD0, 0x15, // 0x2F4: Draw K
+    0x12, 0xF6, // 0x2F6: Loop
+    0x00, 0xE0, // 0x2F8: Clear the display
+    0xFE, 0x29, // 0x2FA: Point I at the number of the failed test
+    0x60, 0x1C, // 0x2FC: Load 28 into register 0
+    0x61, 0x0D, // 0x2FE: Load 13 into register 1
+    0xD0, 0x15, // 0x300: Draw it
+    0x13, 0x02, // 0x302: Loop
+    0x63, 0x42, // 0x304: Load 0x42 into register 3
+    0x00, 0xEE, // 0x306: Return
+    0x3C, 0x42, 0x42, 0x42, 0x3C, // 0x308: O
+    0x44, 0x48, 0x70, 0x48, 0x44, // 0x30D: K
+];
+
+const TESTS: u8 = 15;
+const ADDRESS_PASS: u16 = 0x2F6;
+const SPRITE_OK: [[u8; 5]; 2] = [
+    [0x3C, 0x42, 0x42, 0x42, 0x3C],
+    [0x44, 0x48, 0x70, 0x48, 0x44],
+];
+
+const FRAMES: usize = 30;
+const INSTRUCTIONS_PER_FRAME: usize = 10;
+
+/// Display with `OK` drawn at the same place as the ROM does.
+fn all_pass_display() -> Result<Chip8> {
+    let mut chip = Chip8::new(Config::default());
+    for (n, sprite) in SPRITE_OK.iter().enumerate() {
+        for (y, row) in sprite.iter().enumerate() {
+            for x in 0..8 {
+                if row & (0x80 >> x) != 0 {
+                    chip.set_pixel(28 + 8 * n + x, 13 + y, true)?;
+                }
+            }
+        }
+    }
+
+    Ok(chip)
+}
+
+#[rstest]
+#[case::modern(Config::modern())]
+#[case::cosmac_vip(Config::cosmac_vip())]
+#[case::chip48(Config::chip48())]
+#[case::superchip(Config::superchip())]
+fn opcode_test_passes(#[case] config: Config) -> Result<()> {
+    let mut chip = Chip8::from_rom(ROM, config)?;
+
+    for _ in 0..FRAMES {
+        chip.advance_frame(INSTRUCTIONS_PER_FRAME)?;
+    }
+
+    assert_eq!(
+        chip.pc(),
+        ADDRESS_PASS,
+        "failed test {:?}",
+        chip.register(0xE)
+    );
+    assert_eq!(chip.register(0xE), Some(TESTS));
+    assert_eq!(
+        chip.display_checksum(),
+        all_pass_display()?.display_checksum()
+    );
+    assert_eq!(chip.get_pixel(30, 13), Some(true));
+    assert_eq!(chip.get_pixel(28, 13), Some(false));
+    assert_eq!(chip.get_pixel(38, 15), Some(true));
+    assert_eq!(chip.get_pixel(0, 0), Some(false));
+    assert_eq!(
+        chip.get_pixel(
+            Memory::SIZE_DISPLAY_WIDTH - 1,
+            Memory::SIZE_DISPLAY_HEIGHT - 1
+        ),
+        Some(false)
+    );
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use chip_8::{Chip8, Watch};
+use clap::{Parser, ValueEnum};
+
+use crate::{rom::Header, ui::Theme};
+
+/// Terminal emulator of CHIP-8.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Paths to the ROMs switched with [ and ], or `-` to read one from the standard input.
+    /// The quirks of the first ROM that loads are kept for the others.
+    #[arg(default_value = "./roms/3-corax+.ch8", num_args = 1..)]
+    pub rom: Vec<PathBuf>,
+    /// Continue a ROM where it was left when switching back to it, instead of starting it over.
+    #[arg(long)]
+    pub keep_state: bool,
+    /// Colors of the pixel display.
+    #[arg(long, value_enum, default_value_t = ThemeName::Mono)]
+    pub theme: ThemeName,
+    /// Draw with the default colors of the terminal only, lit pixels in reverse video, for terminals with few colors.
+    #[arg(long)]
+    pub no_color: bool,
+    /// How the pixel display is drawn, sixel falls back to half blocks when the terminal does not seem to support it.
+    #[arg(long, value_enum, default_value_t = Render::HalfBlock)]
+    pub render: Render,
+    /// TOML file with emulation quirks and keypad layout, see `config.example.toml`.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// How many instructions run each 60 Hz frame, can be changed with + and - while running.
+    #[arg(
+        long,
+        default_value_t = Chip8::instructions_per_frame(Chip8::DEFAULT_INSTRUCTIONS_PER_SECOND) as u16,
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub ipf: u16,
+    /// Run the frames missed when drawing can not keep up along with the next one,
+    /// so the timers and instructions stay in real time on slow terminals.
+    #[arg(long)]
+    pub auto_tune: bool,
+    /// How many times faster instructions run while turbo is toggled with Space.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    pub turbo: u16,
+    /// How many frames can be stepped back with Backspace, 0 disables rewinding.
+    #[arg(long, default_value_t = 600)]
+    pub rewind: usize,
+    /// Start paused, so the program can be stepped through with N from its first instruction.
+    #[arg(long, visible_alias = "debug")]
+    pub pause: bool,
+    /// Run this many instructions as fast as possible without the interface and print how long it took.
+    #[arg(long, value_name = "N")]
+    pub bench: Option<usize>,
+    /// Run this many instructions without the interface and print which opcodes were executed.
+    #[arg(long, value_name = "N", conflicts_with = "bench")]
+    pub coverage: Option<usize>,
+    /// Print the disassembled ROM instead of running it.
+    #[arg(long)]
+    pub disasm: bool,
+    /// Drop this many bytes from the start of ROM files, or `auto` to detect RAM dumps and load address prefixes.
+    #[arg(long, value_name = "N", value_parser = parse_header)]
+    pub skip_header: Option<Header>,
+    /// Log every write to RAM and registers to a file as `frame,pc,addr,value` lines, slows the emulation down.
+    #[arg(long, value_name = "FILE")]
+    pub trace_writes: Option<PathBuf>,
+    /// Seed of the random numbers, so runs can be repeated. Random every run if not set.
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+    /// Pause when an instruction changes a register (`V3`) or a byte of RAM (`0x300`), can be repeated.
+    #[arg(long, value_name = "LOCATION", value_parser = parse_watch)]
+    pub watch: Vec<Watch>,
+}
+
+fn parse_header(s: &str) -> Result<Header, String> {
+    if s == "auto" {
+        return Ok(Header::Auto);
+    }
+    s.parse()
+        .map(Header::Bytes)
+        .map_err(|e| format!("invalid length: {e}"))
+}
+
+fn parse_watch(s: &str) -> Result<Watch, String> {
+    if let Some(vx) = s.strip_prefix(['V', 'v']) {
+        return usize::from_str_radix(vx, 16)
+            .map(Watch::Register)
+            .map_err(|e| format!("invalid register: {e}"));
+    }
+    u16::from_str_radix(s.trim_start_matches("0x"), 16)
+        .map(Watch::Ram)
+        .map_err(|e| format!("invalid address: {e}"))
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Render {
+    /// Two pixels per character using half blocks.
+    HalfBlock,
+    /// One pixel per character using full blocks, for terminals where half blocks are far from square.
+    FullBlock,
+    /// Actual pixels using sixel graphics.
+    Sixel,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeName {
+    /// Amber phosphor.
+    Amber,
+    /// Green phosphor.
+    Green,
+    /// White on black.
+    Mono,
+}
+
+impl From<ThemeName> for Theme {
+    fn from(value: ThemeName) -> Self {
+        match value {
+            ThemeName::Amber => Theme::AMBER,
+            ThemeName::Green => Theme::GREEN,
+            ThemeName::Mono => Theme::MONO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    #[case("auto", Header::Auto)]
+    #[case("512", Header::Bytes(512))]
+    fn parse_header_valid(#[case] s: &str, #[case] expected: Header) -> Result<()> {
+        assert_eq!(parse_header(s), Ok(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("V3", Watch::Register(0x3))]
+    #[case("vf", Watch::Register(0xF))]
+    #[case("0x300", Watch::Ram(0x300))]
+    #[case("2A0", Watch::Ram(0x2A0))]
+    fn parse_watch_valid(#[case] s: &str, #[case] expected: Watch) -> Result<()> {
+        assert_eq!(parse_watch(s), Ok(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    fn parse_watch_invalid(#[values("", "V", "Vx", "0xZZ")] s: &str) -> Result<()> {
+        assert!(parse_watch(s).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+
+use chip_8::{Chip8, InstructionError};
+
+/// Run instructions as fast as possible, updating the timers every `instructions_per_frame` of them.
+///
+/// Returns how long it took.
+///
+/// # Arguments
+///
+/// * `chip` - Machine with a loaded ROM.
+/// * `instructions` - How many fetch decode execute cycles to perform.
+/// * `instructions_per_frame` - How many cycles run between timer updates.
+pub fn run(
+    chip: &mut Chip8,
+    instructions: usize,
+    instructions_per_frame: usize,
+) -> Result<Duration, InstructionError> {
+    let start = Instant::now();
+
+    for _ in 0..instructions / instructions_per_frame {
+        chip.advance_frame(instructions_per_frame)?;
+    }
+    chip.tick_instructions(instructions % instructions_per_frame)?;
+
+    Ok(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::Config;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn run_runs_instructions(#[values(1, 7, 10)] instructions_per_frame: usize) -> Result<()> {
+        let mut target = Chip8::new(Config::default());
+        target.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Jump back
+        ]);
+
+        run(&mut target, 25, instructions_per_frame)?;
+
+        assert_eq!(target.register(0x0), Some(13));
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_stops_on_error() -> Result<()> {
+        let mut target = Chip8::new(Config::default());
+        target.load(&[
+            0x00, 0x00, // Call a machine code routine
+        ]);
+
+        assert!(run(&mut target, 25, 10).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chip_8::Config;
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::keymap::KeyMap;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("could not read config: {0}")]
+    Io(#[from] io::Error),
+    #[error("config is invalid: {0}")]
+    Invalid(#[from] toml::de::Error),
+    #[error("sidecar is invalid: {0}")]
+    InvalidSidecar(#[from] serde_json::Error),
+}
+
+/// Everything that can be configured from a file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// Emulation quirks, stored at the top level of the file.
+    pub quirks: Config,
+    /// Keypad layout, stored in the `[keys]` table.
+    /// Replaces the default layout entirely when present.
+    pub keys: KeyMap,
+}
+
+/// Parse configuration.
+///
+/// Missing keys fall back to the defaults, unknown keys are rejected.
+pub fn from_str(config: &str) -> Result<Settings, ConfigError> {
+    let mut table: Table = toml::from_str(config)?;
+    let keys = table
+        .remove("keys")
+        .map(Value::try_into)
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(Settings {
+        quirks: Value::Table(table).try_into()?,
+        keys,
+    })
+}
+
+/// Read configuration from a TOML file.
+pub fn load(path: impl AsRef<Path>) -> Result<Settings, ConfigError> {
+    from_str(&fs::read_to_string(path)?)
+}
+
+/// Where the sidecar of a ROM is stored, `game.ch8` has `game.ch8.json`.
+pub fn sidecar_path(rom: impl AsRef<Path>) -> PathBuf {
+    let mut path = rom.as_ref().as_os_str().to_owned();
+    path.push(".json");
+    path.into()
+}
+
+/// Parse a JSON sidecar, it has the same structure as the TOML configuration.
+pub fn from_json(sidecar: &str) -> Result<Settings, ConfigError> {
+    let mut map: serde_json::Map<String, serde_json::Value> = serde_json::from_str(sidecar)?;
+    let keys = map
+        .remove("keys")
+        .map(serde_json::from_value)
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(Settings {
+        quirks: serde_json::from_value(serde_json::Value::Object(map))?,
+        keys,
+    })
+}
+
+/// Read configuration from the JSON sidecar of a ROM.
+///
+/// Returns [`None`] if the ROM has no sidecar.
+pub fn load_sidecar(rom: impl AsRef<Path>) -> Result<Option<Settings>, ConfigError> {
+    match fs::read_to_string(sidecar_path(rom)) {
+        Ok(sidecar) => Ok(Some(from_json(&sidecar)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use crossterm::event::KeyCode;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn from_str_parses_example() -> Result<()> {
+        let target = from_str(include_str!("../config.example.toml"))?;
+
+        assert_eq!(target.quirks, Config::cosmac_vip());
+        assert_eq!(target.keys.get(KeyCode::Char('a')), Some(0x4));
+        assert_eq!(target.keys.get(KeyCode::Char('q')), Some(0x7));
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_str_defaults_missing_keys() -> Result<()> {
+        let target = from_str("jump_reads_from_vx = true")?;
+
+        assert_eq!(
+            target,
+            Settings {
+                quirks: Config {
+                    jump_reads_from_vx: true,
+                    ..Config::default()
+                },
+                keys: KeyMap::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_str_parses_keys() -> Result<()> {
+        let target = from_str("[keys]\nj = 0x5\nk = 0x8")?;
+
+        assert_eq!(
+            target.keys,
+            KeyMap::try_from(HashMap::from([('j', 0x5), ('k', 0x8)]))?
+        );
+        assert_eq!(target.quirks, Config::default());
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_str_rejects_unknown_keys() -> Result<()> {
+        let target = from_str("shift_ignores_vx = true");
+
+        assert!(matches!(target, Err(ConfigError::Invalid(_))));
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_str_rejects_invalid_chip_key() -> Result<()> {
+        let target = from_str("[keys]\nj = 0x10");
+
+        assert!(matches!(target, Err(ConfigError::Invalid(_))));
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_str_rejects_invalid_timeout_key() -> Result<()> {
+        let target = from_str("wait_key_timeout_key = 0x10");
+
+        assert!(matches!(target, Err(ConfigError::Invalid(_))));
+        assert_eq!(
+            from_str("wait_key_timeout_key = 0xF")?
+                .quirks
+                .wait_key_timeout_key,
+            0xF
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn sidecar_path_appends_json() -> Result<()> {
+        assert_eq!(
+            sidecar_path("roms/game.ch8"),
+            PathBuf::from("roms/game.ch8.json")
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_json_parses_quirks_and_keys() -> Result<()> {
+        let target = from_json(r#"{ "display_wait": true, "keys": { "j": 5 } }"#)?;
+
+        assert_eq!(
+            target,
+            Settings {
+                quirks: Config {
+                    display_wait: true,
+                    ..Config::default()
+                },
+                keys: KeyMap::try_from(HashMap::from([('j', 0x5)]))?,
+            }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_json_rejects_malformed() -> Result<()> {
+        assert!(matches!(
+            from_json(r#"{ "display_wait": 1 }"#),
+            Err(ConfigError::InvalidSidecar(_))
+        ));
+        assert!(matches!(
+            from_json("{"),
+            Err(ConfigError::InvalidSidecar(_))
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    fn load_sidecar_missing() -> Result<()> {
+        let rom = std::env::temp_dir().join("chip_8_load_sidecar_missing.ch8");
+
+        assert_eq!(load_sidecar(rom)?, None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn load_sidecar_present() -> Result<()> {
+        let rom = std::env::temp_dir().join("chip_8_load_sidecar_present.ch8");
+        fs::write(sidecar_path(&rom), r#"{ "jump_reads_from_vx": true }"#)?;
+
+        let target = load_sidecar(&rom);
+        fs::remove_file(sidecar_path(&rom))?;
+
+        assert_eq!(
+            target?.map(|s| s.quirks),
+            Some(Config {
+                jump_reads_from_vx: true,
+                ..Config::default()
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use chip_8::{Chip8, Instruction, InstructionError, Opcode, StepOutcome};
+
+/// How many times each opcode family was executed, by [`Instruction::pattern`].
+pub type Coverage = BTreeMap<&'static str, usize>;
+
+/// Run instructions as fast as possible like [`crate::bench::run`], counting the executed opcode families.
+///
+/// The counts are kept in `coverage` even if an instruction fails, the failed one is not counted.
+///
+/// # Arguments
+///
+/// * `chip` - Machine with a loaded ROM, it needs to record its history.
+/// * `instructions` - How many fetch decode execute cycles to perform.
+/// * `instructions_per_frame` - How many cycles run between timer updates.
+/// * `coverage` - Where the counts are added.
+pub fn run(
+    chip: &mut Chip8,
+    instructions: usize,
+    instructions_per_frame: usize,
+    coverage: &mut Coverage,
+) -> Result<(), InstructionError> {
+    for n in 1..=instructions {
+        if chip.advance_instruction()? != StepOutcome::Blocked {
+            if let Some((_, instruction)) = chip.history().back() {
+                *coverage.entry(instruction.pattern()).or_default() += 1;
+            }
+        }
+        if n % instructions_per_frame == 0 {
+            chip.advance_timer();
+        }
+    }
+
+    Ok(())
+}
+
+/// Every opcode family the emulator knows, in the order of their first opcodes.
+fn families() -> Vec<&'static str> {
+    let mut families = Vec::new();
+    for word in 0..=u16::MAX {
+        if let Ok(instruction) = Instruction::decode(Opcode::from(word), 0) {
+            if !families.contains(&instruction.pattern()) {
+                families.push(instruction.pattern());
+            }
+        }
+    }
+    families
+}
+
+/// Print a table of the opcode families with how many times they were executed, `-` for ones never hit,
+/// followed by a summary.
+pub fn write(coverage: &Coverage, out: &mut impl Write) -> io::Result<()> {
+    let families = families();
+
+    writeln!(out, "Opcode  Executed")?;
+    for family in &families {
+        match coverage.get(family) {
+            Some(count) => writeln!(out, "{family}  {count:>8}")?,
+            None => writeln!(out, "{family}  {:>8}", "-")?,
+        }
+    }
+    writeln!(
+        out,
+        "Covered {} of {} opcode families",
+        families
+            .iter()
+            .filter(|f| coverage.contains_key(*f))
+            .count(),
+        families.len()
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::Config;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn run_counts_executed_families() -> Result<()> {
+        let rom = [
+            0x61, 0x02, // Load 2 into register 1
+            0x71, 0x03, // Add 3 to register 1
+            0x31, 0x05, // Skip the next instruction if register 1 is 5
+            0x00, 0xE0, // Clear the display, skipped the first time
+            0x12, 0x02, // Jump to the addition
+        ];
+        let mut chip = Chip8::from_rom(&rom, Config::default())?.with_history(1);
+        let mut target = Coverage::new();
+
+        run(&mut chip, 7, 3, &mut target)?;
+
+        assert_eq!(
+            target,
+            Coverage::from([
+                ("00E0", 1),
+                ("1nnn", 1),
+                ("3xnn", 2),
+                ("6xnn", 1),
+                ("7xnn", 2)
+            ])
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_keeps_counts_on_error() -> Result<()> {
+        let rom = [
+            0x61, 0x02, // Load 2 into register 1
+            0x01, 0x23, // Call a machine code routine
+        ];
+        let mut chip = Chip8::from_rom(&rom, Config::default())?.with_history(1);
+        let mut target = Coverage::new();
+
+        assert!(run(&mut chip, 2, 1, &mut target).is_err());
+        assert_eq!(target, Coverage::from([("6xnn", 1)]));
+        Ok(())
+    }
+
+    #[rstest]
+    fn write_lists_uncovered_families() -> Result<()> {
+        let mut out = Vec::new();
+
+        write(&Coverage::from([("00E0", 3), ("Dxyn", 12)]), &mut out)?;
+
+        let out = String::from_utf8(out)?;
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines[..4],
+            [
+                "Opcode  Executed",
+                "0nnn         -",
+                "00E0         3",
+                "00EE         -"
+            ]
+        );
+        assert!(lines.contains(&"Dxyn        12"));
+        assert_eq!(lines.last(), Some(&"Covered 2 of 40 opcode families"));
+        Ok(())
+    }
+}
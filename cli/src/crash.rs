@@ -0,0 +1,141 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    sync::Mutex,
+};
+
+use chip_8::Chip8;
+
+/// Where the state of the machine is written when the frontend panics.
+pub const PATH: &str = "crash.log";
+
+/// Machine as of the last frame, written by [`dump`].
+static LAST_STATE: Mutex<Option<Chip8>> = Mutex::new(None);
+
+/// Remember the state of the machine in case a panic happens, should be called once per frame.
+pub fn record(chip: &Chip8) {
+    if let Ok(mut state) = LAST_STATE.lock() {
+        match &mut *state {
+            Some(state) => state.clone_from(chip),
+            None => *state = Some(chip.clone()),
+        }
+    }
+}
+
+/// Write the registers and the recorded instructions of the machine, from the oldest to the newest.
+///
+/// # Arguments
+///
+/// * `chip` - Machine to describe.
+/// * `panic` - What went wrong.
+/// * `out` - Where to write.
+pub fn write(chip: &Chip8, panic: &impl Display, out: &mut impl Write) -> io::Result<()> {
+    let memory = chip.memory();
+
+    writeln!(out, "{panic}")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "pc {:#05X}  i {:#05X}  dt {:#04X}  st {:#04X}",
+        memory.pc, memory.i, memory.dt, memory.st
+    )?;
+    for (vx, value) in memory.v.iter().enumerate() {
+        writeln!(out, "V{vx:X} {value:#04X}")?;
+    }
+    write!(out, "stack")?;
+    for address in &memory.stack {
+        write!(out, " {address:#05X}")?;
+    }
+    writeln!(out)?;
+
+    writeln!(out)?;
+    writeln!(out, "Last instructions:")?;
+    for (address, instruction) in chip.history() {
+        writeln!(out, "{address:#05X}: {instruction}")?;
+    }
+
+    out.flush()
+}
+
+/// Write the last recorded state into [`PATH`], for the panic hook.
+/// Never panics itself, anything that fails just leaves the log incomplete.
+///
+/// # Returns
+///
+/// Whether the log was written.
+pub fn dump(panic: &impl Display) -> bool {
+    // The panic could have happened while the state was being recorded.
+    let Ok(state) = LAST_STATE.try_lock() else {
+        return false;
+    };
+    let Some(chip) = &*state else {
+        return false;
+    };
+
+    File::create(PATH)
+        .and_then(|file| write(chip, panic, &mut BufWriter::new(file)))
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::Config;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn write_lists_registers_and_history() -> Result<()> {
+        let mut chip = Chip8::builder()
+            .rom(&[
+                0x60, 0x01, // Load 1 into register 0
+                0xA3, 0x00, // Point I at 0x300
+                0x22, 0x08, // Call the subroutine
+                0x00, 0x00, // Padding
+                0x71, 0x02, // Add 2 to register 1
+            ])
+            .history(2)
+            .build()?;
+        chip.tick_instructions(4)?;
+        let mut out = Vec::new();
+
+        write(&chip, &"panicked at app.rs", &mut out)?;
+
+        let out = String::from_utf8(out)?;
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines[..4],
+            [
+                "panicked at app.rs",
+                "",
+                "pc 0x20A  i 0x300  dt 0x00  st 0x00",
+                "V0 0x01",
+            ]
+        );
+        assert_eq!(lines[4], "V1 0x02");
+        assert_eq!(
+            lines[19..],
+            [
+                "stack 0x206",
+                "",
+                "Last instructions:",
+                "0x204: CALL 0x208",
+                "0x208: ADD V1, 0x02",
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn write_without_history() -> Result<()> {
+        let mut out = Vec::new();
+
+        write(&Chip8::new(Config::default()), &"oops", &mut out)?;
+
+        assert!(String::from_utf8(out)?.ends_with("Last instructions:\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use std::io::{self, Write};
+
+use chip_8::{disassemble, Memory};
+
+/// Print every word of a ROM as `address: opcode  mnemonic`, as if it was loaded at the program start.
+///
+/// Words that are not instructions are printed as `DB 0xNNNN`, a trailing odd byte as `DB 0xNN`.
+///
+/// # Arguments
+///
+/// * `rom` - Program to decode.
+/// * `out` - Where the listing is written.
+pub fn write(rom: &[u8], out: &mut impl Write) -> io::Result<()> {
+    let start = Memory::INDEX_PROGRAM_START as usize;
+    let mut ram = vec![0; start];
+    ram.extend_from_slice(rom);
+
+    let mut end = start;
+    for d in disassemble(&ram, start as u16) {
+        let size = d.instruction.map_or(2, |i| i.size() as usize);
+        let opcode = ram[d.address as usize..][..size]
+            .chunks(2)
+            .map(|word| format!("{:02X}{:02X}", word[0], word[1]))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mnemonic = match d.instruction {
+            Some(instruction) => instruction.to_string(),
+            None => format!("DB 0x{:04X}", d.word),
+        };
+
+        writeln!(out, "0x{:03X}: {opcode:<9}  {mnemonic}", d.address)?;
+        end = d.address as usize + size;
+    }
+    if let Some(byte) = ram.get(end) {
+        writeln!(out, "0x{end:03X}: {byte:02X}{:<7}  DB 0x{byte:02X}", "")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn write_lists_program() -> Result<()> {
+        let rom = [
+            0x00, 0xE0, // Clear the display
+            0x61, 0x02, // Load 2 into register 1
+            0xF0, 0x00, 0x12, 0x34, // Load a long address into I
+            0xD1, 0x25, // Draw a sprite
+            0xFF, 0xFF, // Data
+            0x12, 0x00, // Jump back
+            0xAB, // Trailing byte
+        ];
+        let mut out = Vec::new();
+
+        write(&rom, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            concat!(
+                "0x200: 00E0       CLS\n",
+                "0x202: 6102       LD V1, 0x02\n",
+                "0x204: F000 1234  LD I, 0x1234\n",
+                "0x208: D125       DRW V1, V2, 0x5\n",
+                "0x20A: FFFF       DB 0xFFFF\n",
+                "0x20C: 1200       JP 0x200\n",
+                "0x20E: AB         DB 0xAB\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn write_empty_rom() -> Result<()> {
+        let mut out = Vec::new();
+
+        write(&[], &mut out)?;
+
+        assert_eq!(out, b"");
+        Ok(())
+    }
+}
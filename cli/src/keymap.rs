@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum KeyMapError {
+    #[error("key {key:#X} bound to {host:?} is not in 0-F range")]
+    InvalidKey { host: char, key: u8 },
+}
+
+/// Mapping of host keyboard keys to CHIP-8 keys.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "HashMap<char, u8>")]
+pub struct KeyMap(HashMap<char, u8>);
+
+impl KeyMap {
+    /// The usual layout that places the 4x4 keypad on the left side of a QWERTY keyboard.
+    ///
+    /// ```text
+    /// 1 2 3 4    1 2 3 C
+    /// q w e r    4 5 6 D
+    /// a s d f    7 8 9 E
+    /// z x c v    A 0 B F
+    /// ```
+    pub fn qwerty() -> Self {
+        Self(HashMap::from([
+            ('1', 0x1),
+            ('2', 0x2),
+            ('3', 0x3),
+            ('4', 0xC),
+            ('q', 0x4),
+            ('w', 0x5),
+            ('e', 0x6),
+            ('r', 0xD),
+            ('a', 0x7),
+            ('s', 0x8),
+            ('d', 0x9),
+            ('f', 0xE),
+            ('z', 0xA),
+            ('x', 0x0),
+            ('c', 0xB),
+            ('v', 0xF),
+        ]))
+    }
+
+    /// CHIP-8 key bound to a host key.
+    pub fn get(&self, code: KeyCode) -> Option<u8> {
+        match code {
+            KeyCode::Char(c) => self.0.get(&c.to_ascii_lowercase()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Host key bound to a CHIP-8 key, the first one alphabetically if there are several.
+    pub fn host(&self, key: u8) -> Option<char> {
+        self.0
+            .iter()
+            .filter(|(_, &k)| k == key)
+            .map(|(&host, _)| host)
+            .min()
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::qwerty()
+    }
+}
+
+impl TryFrom<HashMap<char, u8>> for KeyMap {
+    type Error = KeyMapError;
+
+    fn try_from(value: HashMap<char, u8>) -> Result<Self, Self::Error> {
+        if let Some((&host, &key)) = value.iter().find(|(_, &key)| key > 0xF) {
+            return Err(KeyMapError::InvalidKey { host, key });
+        }
+
+        Ok(Self(
+            value
+                .into_iter()
+                .map(|(host, key)| (host.to_ascii_lowercase(), key))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn qwerty_covers_keypad() -> Result<()> {
+        let target = KeyMap::qwerty();
+
+        let mut keys: Vec<_> = target.0.values().copied().collect();
+        keys.sort_unstable();
+
+        assert_eq!(keys, (0x0..=0xF).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[rstest]
+    fn get_ignores_case(#[values('q', 'Q')] host: char) -> Result<()> {
+        assert_eq!(KeyMap::qwerty().get(KeyCode::Char(host)), Some(0x4));
+        Ok(())
+    }
+
+    #[rstest]
+    fn get_unbound() -> Result<()> {
+        assert_eq!(KeyMap::qwerty().get(KeyCode::Char('p')), None);
+        assert_eq!(KeyMap::qwerty().get(KeyCode::Esc), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn host_finds_first_binding() -> Result<()> {
+        let target = KeyMap::try_from(HashMap::from([('k', 0x4), ('j', 0x4)]))?;
+
+        assert_eq!(target.host(0x4), Some('j'));
+        assert_eq!(target.host(0x5), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn try_from_remaps() -> Result<()> {
+        let target = KeyMap::try_from(HashMap::from([('A', 0x4), ('z', 0x5)]))?;
+
+        assert_eq!(target.get(KeyCode::Char('a')), Some(0x4));
+        assert_eq!(target.get(KeyCode::Char('z')), Some(0x5));
+        assert_eq!(target.get(KeyCode::Char('q')), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn try_from_rejects_invalid_key() -> Result<()> {
+        assert_eq!(
+            KeyMap::try_from(HashMap::from([('a', 0x10)])),
+            Err(KeyMapError::InvalidKey {
+                host: 'a',
+                key: 0x10
+            })
+        );
+        Ok(())
+    }
+}
@@ -1,89 +1,190 @@
-#![feature(array_chunks)]
 #![feature(iter_array_chunks)]
 
+mod args;
+mod bench;
+mod config;
+mod coverage;
+mod crash;
+mod disasm;
+mod keymap;
+mod playlist;
+mod recorder;
+mod rewind;
+mod rom;
+mod savestate;
+mod screenshot;
+mod sixel;
 mod timer;
+mod trace;
+mod tuner;
 mod ui;
 mod waiter;
 
 use std::{
-    fs,
-    sync::{Arc, Mutex},
-    thread,
+    io::{self, stdout},
     time::Duration,
 };
 
+use args::{Args, Render};
 use chip_8::Chip8;
+use clap::Parser;
 use ui::AppWidget;
 use waiter::Waiter;
 
-const INSTRUCTIONS_PER_SECOND: usize = 50;
-const FRAMES_PER_SECOND: usize = 60;
-
-const ROM_PATH: &str = "./roms/3-corax+.ch8";
+const FRAMES_PER_SECOND: usize = Chip8::FREQUENCY_TIMER_UPDATE;
 
 fn main() -> Result<(), i32> {
-    let rom = fs::read(ROM_PATH).map_err(|_| 2)?;
-
-    let mut chip = Chip8::default();
-    chip.load(&rom);
+    let args = Args::parse();
+
+    let mut playlist = playlist::Playlist::new(args.rom);
+    let opened = playlist.open(|path| playlist::read(path, io::stdin(), args.skip_header));
+    for skipped in &opened.skipped {
+        eprintln!("{skipped}");
+    }
+    let rom = opened.loaded.ok_or(2)?;
+    let rom_path = playlist.current().expect("loaded entry").to_path_buf();
+
+    if args.disasm {
+        return disasm::write(&rom, &mut stdout().lock()).map_err(|_| 1);
+    }
+
+    let mut warning = None;
+    let settings = match args.config {
+        Some(path) => Some(config::load(path).map_err(|e| {
+            eprintln!("{e}");
+            2
+        })?),
+        None if rom_path.as_os_str() == rom::STDIN => None,
+        None => config::load_sidecar(&rom_path).unwrap_or_else(|e| {
+            warning = Some(format!("Ignoring sidecar: {e}"));
+            None
+        }),
+    }
+    .unwrap_or_default();
+
+    let mut builder = Chip8::builder()
+        .config(settings.quirks)
+        .rom(&rom)
+        .history(Chip8::HISTORY_CAPACITY);
+    if let Some(seed) = args.seed {
+        builder = builder.rng(seed);
+    }
+    let mut chip = builder.build().map_err(|e| {
+        eprintln!("{e}");
+        2
+    })?;
+    for watch in args.watch {
+        chip.add_watch(watch).map_err(|e| {
+            eprintln!("{e}");
+            2
+        })?;
+    }
+
+    if let Some(instructions) = args.bench {
+        if let Some(warning) = warning {
+            eprintln!("{warning}");
+        }
+        let elapsed = bench::run(&mut chip, instructions, args.ipf as usize).map_err(|e| {
+            eprintln!("{e}");
+            1
+        })?;
+        println!(
+            "Ran {instructions} instructions in {:.3} s ({:.0} instructions/s)",
+            elapsed.as_secs_f64(),
+            instructions as f64 / elapsed.as_secs_f64()
+        );
+        return Ok(());
+    }
+
+    if let Some(instructions) = args.coverage {
+        if let Some(warning) = warning {
+            eprintln!("{warning}");
+        }
+        let mut coverage = coverage::Coverage::new();
+        let result = coverage::run(&mut chip, instructions, args.ipf as usize, &mut coverage);
+        coverage::write(&coverage, &mut stdout().lock()).map_err(|_| 1)?;
+        return result.map_err(|e| {
+            eprintln!("{e}");
+            1
+        });
+    }
+
+    let sixel = args.render == Render::Sixel && sixel::supported();
+    if args.render == Render::Sixel && !sixel {
+        warning.get_or_insert_with(|| {
+            "Sixel graphics were not detected, using half blocks".to_string()
+        });
+    }
+
+    let write_trace = args
+        .trace_writes
+        .map(|path| {
+            trace::WriteTrace::create(&path).map_err(|e| {
+                eprintln!("Could not trace writes to {}: {e}", path.display());
+                2
+            })
+        })
+        .transpose()?;
 
+    let key_releases = ui::supports_key_releases();
     let mut terminal = ui::start_ui().map_err(|_| 1)?;
     ui::panic_hook();
 
-    let app = Arc::new(Mutex::new(ui::App::new(
+    let mut app = ui::App::new(
         chip,
-        INSTRUCTIONS_PER_SECOND,
+        ui::AppOptions {
+            theme: args.theme.into(),
+            color: !args.no_color,
+            keys: settings.keys,
+            key_releases,
+            turbo_factor: args.turbo as usize,
+            rewind_frames: args.rewind,
+            paused: args.pause,
+            sixel,
+            full_block: args.render == Render::FullBlock,
+            playlist,
+            keep_state: args.keep_state,
+            skip_header: args.skip_header,
+            write_trace,
+            auto_tune: args.auto_tune,
+        },
+        args.ipf as usize,
         FRAMES_PER_SECOND,
-    )));
-
-    let draw_handle = {
-        let app_draw = app.clone();
-        let mut waiter = Waiter::new(Duration::from_secs_f64(1f64 / FRAMES_PER_SECOND as f64));
-
-        thread::spawn(move || loop {
-            waiter.start();
-
-            {
-                let app = app_draw.lock().expect("handle on the app in draw loop");
-                if app.state() == ui::AppState::End {
-                    ui::end_ui().expect("draw end");
-                    break;
-                }
-                terminal
-                    .draw(|f| {
-                        f.render_widget(AppWidget { app: &app }, f.size());
-                    })
-                    .expect("draw loop");
-            }
+    );
+    app.message = warning;
 
-            waiter.end();
-            waiter.cycle();
-        })
-    };
-
-    {
-        let mut waiter = Waiter::new(Duration::from_secs_f64(
-            1f64 / INSTRUCTIONS_PER_SECOND as f64,
-        ));
-
-        loop {
-            waiter.start();
+    let sixel_scale = sixel::scale();
+    let mut sixel_shown = false;
 
-            {
-                let mut app = app.lock().expect("handle on the app in update loop");
+    let mut waiter = Waiter::new(Duration::from_secs_f64(1f64 / FRAMES_PER_SECOND as f64));
+    loop {
+        waiter.start();
 
-                app.update();
-                if app.state() == ui::AppState::End {
-                    break;
-                }
+        app.update();
+        crash::record(&app.chip);
+        if app.state() == ui::AppState::End {
+            ui::end_ui().map_err(|_| 1)?;
+            break;
+        }
+        terminal
+            .draw(|f| {
+                f.render_widget(AppWidget { app: &app }, f.size());
+            })
+            .expect("draw loop");
+        match app.sixel_area() {
+            Some(area) => {
+                let image = sixel::encode(&app.chip.frame_buffer(), app.theme, sixel_scale);
+                sixel::draw(&mut stdout(), area, &image).expect("draw sixel");
             }
-
-            waiter.end();
-            waiter.cycle();
+            // Cells that stay blank would keep showing the image.
+            None if sixel_shown => terminal.clear().expect("clear sixel"),
+            None => {}
         }
-    };
+        sixel_shown = app.sixel_area().is_some();
 
-    draw_handle.join().map_err(|_| 2)?;
+        waiter.end();
+        waiter.cycle();
+    }
 
     Ok(())
 }
@@ -0,0 +1,223 @@
+use std::{
+    fmt::Display,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::rom::{self, Header};
+
+/// ROMs passed on the command line, switched with `[` and `]`.
+#[derive(Debug, Default)]
+pub struct Playlist {
+    paths: Vec<PathBuf>,
+    current: usize,
+}
+
+/// Result of moving through a [`Playlist`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Switch<T> {
+    /// What the entry was loaded into, [`None`] if no entry could be loaded.
+    pub loaded: Option<T>,
+    /// Why the entries before the loaded one were skipped, one warning per entry.
+    pub skipped: Vec<String>,
+}
+
+impl Playlist {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths, current: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Position of the current entry, starting at 0.
+    pub fn index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> Option<&Path> {
+        self.paths.get(self.current).map(PathBuf::as_path)
+    }
+
+    /// Load the first entry that can be loaded, starting with the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `load` - Loads an entry, entries that fail are skipped.
+    pub fn open<T, E: Display>(&mut self, load: impl FnMut(&Path) -> Result<T, E>) -> Switch<T> {
+        self.find(0..self.len(), true, load)
+    }
+
+    /// Load the next or the previous entry that can be loaded, wrapping around the ends.
+    /// Stays on the current entry if none can be loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - Whether to move to the next entry instead of the previous one.
+    /// * `load` - Loads an entry, entries that fail are skipped.
+    pub fn advance<T, E: Display>(
+        &mut self,
+        forward: bool,
+        load: impl FnMut(&Path) -> Result<T, E>,
+    ) -> Switch<T> {
+        // The current entry is tried last, so a single working entry is restarted.
+        self.find(1..=self.len(), forward, load)
+    }
+
+    fn find<T, E: Display>(
+        &mut self,
+        offsets: impl Iterator<Item = usize>,
+        forward: bool,
+        mut load: impl FnMut(&Path) -> Result<T, E>,
+    ) -> Switch<T> {
+        let len = self.len();
+        let mut skipped = Vec::new();
+
+        for offset in offsets {
+            let index = if forward {
+                (self.current + offset) % len
+            } else {
+                (self.current + len - offset % len) % len
+            };
+            let path = &self.paths[index];
+            match load(path) {
+                Ok(loaded) => {
+                    self.current = index;
+                    return Switch {
+                        loaded: Some(loaded),
+                        skipped,
+                    };
+                }
+                Err(e) => skipped.push(format!("Skipped {}: {e}", path.display())),
+            }
+        }
+
+        Switch {
+            loaded: None,
+            skipped,
+        }
+    }
+}
+
+/// Read a ROM like [`rom::read`] without its header, if given.
+/// An empty one is an error since there is nothing to run.
+pub fn read(path: &Path, stdin: impl Read, header: Option<Header>) -> io::Result<Vec<u8>> {
+    let mut rom = rom::read(path, stdin)?;
+    if let Some(header) = header {
+        rom = rom::strip_header(rom, header)?;
+    }
+    if rom.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "ROM is empty"));
+    }
+
+    Ok(rom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[fixture]
+    fn target() -> Playlist {
+        Playlist::new(vec!["a.ch8".into(), "b.ch8".into(), "c.ch8".into()])
+    }
+
+    /// Loads every entry except `b.ch8`.
+    fn load(path: &Path) -> Result<String, &'static str> {
+        match path.to_str() {
+            Some("b.ch8") => Err("broken"),
+            path => Ok(path.unwrap_or_default().to_string()),
+        }
+    }
+
+    #[rstest]
+    fn advance_skips_entries_that_fail(mut target: Playlist) -> Result<()> {
+        assert_eq!(
+            target.advance(true, load),
+            Switch {
+                loaded: Some("c.ch8".to_string()),
+                skipped: vec!["Skipped b.ch8: broken".to_string()],
+            }
+        );
+        assert_eq!(target.index(), 2);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_wraps_around(mut target: Playlist) -> Result<()> {
+        assert_eq!(
+            target.advance(false, load).loaded,
+            Some("c.ch8".to_string())
+        );
+        assert_eq!(target.advance(true, load).loaded, Some("a.ch8".to_string()));
+        assert_eq!(target.index(), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_restarts_only_working_entry(mut target: Playlist) -> Result<()> {
+        let switch = target.advance(true, |path| match path.to_str() {
+            Some("a.ch8") => Ok(()),
+            _ => Err("broken"),
+        });
+
+        assert_eq!(switch.loaded, Some(()));
+        assert_eq!(switch.skipped.len(), 2);
+        assert_eq!(target.index(), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_stays_when_nothing_loads(mut target: Playlist) -> Result<()> {
+        target.advance(true, load);
+
+        let switch = target.advance(false, |_| Err::<(), _>("broken"));
+
+        assert_eq!(switch.loaded, None);
+        assert_eq!(switch.skipped.len(), 3);
+        assert_eq!(target.index(), 2);
+        Ok(())
+    }
+
+    #[rstest]
+    fn open_starts_with_current(mut target: Playlist) -> Result<()> {
+        assert_eq!(target.open(load).loaded, Some("a.ch8".to_string()));
+        assert_eq!(target.index(), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn advance_empty_playlist() -> Result<()> {
+        let mut target = Playlist::default();
+
+        assert_eq!(
+            target.advance(true, load),
+            Switch {
+                loaded: None,
+                skipped: vec![],
+            }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn read_rejects_empty_rom() -> Result<()> {
+        assert!(read(Path::new(rom::STDIN), [].as_slice(), None).is_err());
+        assert!(read(
+            Path::new(rom::STDIN),
+            [0x02, 0x00].as_slice(),
+            Some(Header::Auto)
+        )
+        .is_err());
+        assert_eq!(
+            read(Path::new(rom::STDIN), [0x12].as_slice(), None)?,
+            [0x12]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fs, path::Path};
+
+use chip_8::Memory;
+use gif::{Encoder, EncodingError, Frame as GifFrame, Repeat};
+
+use crate::ui::{rgb, Theme};
+
+/// Where the recording is stored.
+pub const PATH: &str = "recording.gif";
+
+/// How many image pixels are used for each display pixel.
+pub const SCALE: u16 = 5;
+
+/// Display in `[y][x]` format.
+pub type Frame = [[bool; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+
+/// Collects rendered frames up to a fixed limit.
+pub struct Recorder {
+    frames: Vec<Frame>,
+    capacity: usize,
+}
+
+impl Recorder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add a frame to the recording.
+    /// Frames past the capacity are dropped.
+    pub fn push(&mut self, frame: Frame) {
+        if self.frames.len() < self.capacity {
+            self.frames.push(frame);
+        }
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+/// Encode frames as an infinitely looping animated GIF.
+///
+/// # Arguments
+///
+/// * `frames` - Frames to encode.
+/// * `theme` - Colors of lit and unlit pixels.
+/// * `fps` - How many frames are displayed per second.
+/// * `scale` - How many image pixels are used for each display pixel.
+pub fn encode(
+    frames: &[Frame],
+    theme: Theme,
+    fps: usize,
+    scale: u16,
+) -> Result<Vec<u8>, EncodingError> {
+    let width = Memory::SIZE_DISPLAY_WIDTH as u16 * scale;
+    let height = Memory::SIZE_DISPLAY_HEIGHT as u16 * scale;
+    let palette = [rgb(theme.bg), rgb(theme.fg)].concat();
+    // GIF delays are whole centiseconds, so each frame ends at the first one after its real end,
+    // which carries the remainder over to the next frames and keeps the overall rate.
+    let end = |frame: usize| (frame * 100).div_ceil(fps);
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = Encoder::new(&mut bytes, width, height, &palette)?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        for (index, frame) in frames.iter().enumerate() {
+            let pixels: Vec<u8> = (0..height as usize)
+                .flat_map(|y| {
+                    (0..width as usize)
+                        .map(move |x| frame[y / scale as usize][x / scale as usize] as u8)
+                })
+                .collect();
+            let mut frame = GifFrame::from_indexed_pixels(width, height, pixels, None);
+            frame.delay = (end(index + 1) - end(index)) as u16;
+            encoder.write_frame(&frame)?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Save frames as an animated GIF.
+pub fn save(
+    frames: &[Frame],
+    theme: Theme,
+    fps: usize,
+    path: impl AsRef<Path>,
+) -> Result<(), EncodingError> {
+    fs::write(path, encode(frames, theme, fps, SCALE)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    const BLANK: Frame = [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+
+    #[rstest]
+    fn push_stops_at_capacity() -> Result<()> {
+        let mut recorder = Recorder::new(2);
+
+        for _ in 0..3 {
+            recorder.push(BLANK);
+        }
+
+        assert_eq!(recorder.frames().len(), 2);
+        Ok(())
+    }
+
+    #[rstest]
+    fn encode_writes_frames() -> Result<()> {
+        let mut lit = BLANK;
+        lit[0][1] = true;
+        let frames = [BLANK, lit];
+
+        let bytes = encode(&frames, Theme::GREEN, 50, 2)?;
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(bytes.as_slice())?;
+
+        assert_eq!(decoder.width(), Memory::SIZE_DISPLAY_WIDTH as u16 * 2);
+        assert_eq!(decoder.height(), Memory::SIZE_DISPLAY_HEIGHT as u16 * 2);
+        assert_eq!(
+            decoder.global_palette(),
+            Some(
+                [rgb(Theme::GREEN.bg), rgb(Theme::GREEN.fg)]
+                    .concat()
+                    .as_slice()
+            )
+        );
+
+        let first = decoder.read_next_frame()?.expect("first frame").clone();
+        assert_eq!(first.delay, 2);
+        assert!(first.buffer.iter().all(|&p| p == 0));
+
+        let second = decoder.read_next_frame()?.expect("second frame").clone();
+        let width = decoder.width() as usize;
+        assert_eq!(&second.buffer[..6], &[0, 0, 1, 1, 0, 0]);
+        assert_eq!(&second.buffer[width..][..6], &[0, 0, 1, 1, 0, 0]);
+
+        assert!(decoder.read_next_frame()?.is_none());
+        Ok(())
+    }
+
+    #[rstest]
+    fn encode_keeps_frame_rate() -> Result<()> {
+        let bytes = encode(&[BLANK; 60], Theme::GREEN, 60, 1)?;
+
+        let mut decoder = gif::DecodeOptions::new().read_info(bytes.as_slice())?;
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame()? {
+            delays.push(frame.delay);
+        }
+
+        assert_eq!(delays[..3], [2, 2, 1]);
+        assert_eq!(delays.iter().sum::<u16>(), 100);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use chip_8::Checkpoint;
+
+/// Keeps the most recent states of the machine to step backwards through time.
+pub struct Rewind {
+    states: VecDeque<Checkpoint>,
+    capacity: usize,
+}
+
+impl Rewind {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            states: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Remember a state.
+    /// The oldest state is forgotten when the capacity is reached.
+    pub fn push(&mut self, state: Checkpoint) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+    }
+
+    /// Go back in time by forgetting several most recent states.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - How many states to go back.
+    ///
+    /// Returns the oldest forgotten state, or [`None`] if there was nothing to go back to.
+    pub fn rewind(&mut self, steps: usize) -> Option<Checkpoint> {
+        (0..steps).map_while(|_| self.states.pop_back()).last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Forget all states.
+    pub fn clear(&mut self) {
+        self.states.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::Chip8;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    fn state(pc: u8) -> Result<Checkpoint> {
+        let mut chip = Chip8::default();
+        chip.load(&[0x12, pc]); // Jump to `0x2pc`
+        chip.advance_instruction()?;
+        Ok(chip.checkpoint())
+    }
+
+    fn pc(state: Checkpoint) -> u16 {
+        let mut chip = Chip8::default();
+        chip.restore(&state);
+        chip.pc()
+    }
+
+    #[rstest]
+    fn rewind_returns_most_recent() -> Result<()> {
+        let mut target = Rewind::new(10);
+        for pc in 0..3 {
+            target.push(state(pc)?);
+        }
+
+        assert_eq!(target.rewind(1).map(pc), Some(0x202));
+        assert_eq!(target.rewind(1).map(pc), Some(0x201));
+        assert_eq!(target.len(), 1);
+        Ok(())
+    }
+
+    #[rstest]
+    fn rewind_several_steps() -> Result<()> {
+        let mut target = Rewind::new(10);
+        for pc in 0..5 {
+            target.push(state(pc)?);
+        }
+
+        assert_eq!(target.rewind(3).map(pc), Some(0x202));
+        assert_eq!(target.rewind(10).map(pc), Some(0x200));
+        assert_eq!(target.rewind(1).map(pc), None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn push_forgets_oldest() -> Result<()> {
+        let mut target = Rewind::new(2);
+        for pc in 0..3 {
+            target.push(state(pc)?);
+        }
+
+        assert_eq!(target.len(), 2);
+        assert_eq!(target.rewind(2).map(pc), Some(0x201));
+        Ok(())
+    }
+
+    #[rstest]
+    fn push_with_zero_capacity() -> Result<()> {
+        let mut target = Rewind::new(0);
+        target.push(state(0)?);
+
+        assert_eq!(target.rewind(1).map(pc), None);
+        Ok(())
+    }
+}
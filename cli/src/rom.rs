@@ -0,0 +1,129 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use chip_8::Memory;
+
+/// Path that means the ROM should be read from the standard input.
+pub const STDIN: &str = "-";
+
+/// Read ROM bytes from a file, or from `stdin` if the path is [`STDIN`].
+pub fn read(path: impl AsRef<Path>, mut stdin: impl Read) -> io::Result<Vec<u8>> {
+    if path.as_ref() == Path::new(STDIN) {
+        let mut rom = Vec::new();
+        stdin.read_to_end(&mut rom)?;
+        Ok(rom)
+    } else {
+        fs::read(path)
+    }
+}
+
+/// Bytes in front of the program in a ROM file, added by some archives and emulators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Header {
+    /// Guess the length from the contents, see [`detect_header`].
+    Auto,
+    /// Fixed length in bytes.
+    Bytes(usize),
+}
+
+/// Guess the length of the header of a ROM file.
+///
+/// Recognizes dumps of the whole RAM, whose first 512 bytes are an empty interpreter area,
+/// and files that start with the `0x0200` load address.
+/// Returns 0 if there seems to be no header.
+pub fn detect_header(rom: &[u8]) -> usize {
+    let start = Memory::INDEX_PROGRAM_START as usize;
+    if rom.len() > start && rom[..start].iter().all(|&b| b == 0) {
+        start
+    } else if rom.starts_with(&Memory::INDEX_PROGRAM_START.to_be_bytes()) {
+        2
+    } else {
+        0
+    }
+}
+
+/// Remove the header from the start of a ROM file.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::InvalidData`] if the header is longer than the file.
+pub fn strip_header(mut rom: Vec<u8>, header: Header) -> io::Result<Vec<u8>> {
+    let len = match header {
+        Header::Auto => detect_header(&rom),
+        Header::Bytes(len) => len,
+    };
+    if len > rom.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("header of {len} bytes is longer than the ROM"),
+        ));
+    }
+
+    rom.drain(..len);
+    Ok(rom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn read_from_stdin() -> Result<()> {
+        let rom = [0x61, 0x02, 0x71, 0x03, 0x00, 0xFF];
+
+        assert_eq!(read(STDIN, rom.as_slice())?, rom);
+        Ok(())
+    }
+
+    #[rstest]
+    fn read_from_file_ignores_stdin() -> Result<()> {
+        let path = std::env::temp_dir().join("chip_8_rom_read_from_file.ch8");
+        fs::write(&path, [0x12, 0x00])?;
+
+        let target = read(&path, [0xFF].as_slice());
+        fs::remove_file(&path)?;
+
+        assert_eq!(target?, [0x12, 0x00]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::none(Header::Bytes(0), 0)]
+    #[case::load_address(Header::Bytes(2), 2)]
+    #[case::ram_dump(Header::Bytes(512), 512)]
+    fn strip_header_skips_bytes(#[case] header: Header, #[case] len: usize) -> Result<()> {
+        let program = [0x61, 0x02, 0x12, 0x00];
+        let mut rom = vec![0xAA; len];
+        rom.extend_from_slice(&program);
+
+        assert_eq!(strip_header(rom, header)?, program);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::none(&[], 0)]
+    #[case::load_address(&[0x02, 0x00], 2)]
+    #[case::ram_dump(&[0; 512], 512)]
+    fn strip_header_detects(#[case] header: &[u8], #[case] len: usize) -> Result<()> {
+        let program = [0x61, 0x02, 0x12, 0x00];
+        let rom = [header, &program].concat();
+
+        assert_eq!(detect_header(&rom), len);
+        assert_eq!(strip_header(rom, Header::Auto)?, program);
+        Ok(())
+    }
+
+    #[rstest]
+    fn strip_header_longer_than_rom() -> Result<()> {
+        assert!(strip_header(vec![0x12, 0x00], Header::Bytes(3)).is_err());
+        assert_eq!(strip_header(vec![0x12, 0x00], Header::Bytes(2))?, b"");
+        Ok(())
+    }
+}
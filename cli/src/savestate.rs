@@ -0,0 +1,116 @@
+use std::{fs, io, path::Path};
+
+use bincode::Options;
+use chip_8::Chip8;
+use thiserror::Error;
+
+/// Where the quick save is stored.
+pub const PATH: &str = "savestate.bin";
+
+/// Upper bound of the save state size, protects from allocating too much for corrupt files.
+const LIMIT: u64 = 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum SaveStateError {
+    #[error("could not access save state: {0}")]
+    Io(#[from] io::Error),
+    #[error("save state is corrupt: {0}")]
+    Corrupt(#[from] bincode::Error),
+}
+
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(LIMIT)
+}
+
+pub fn to_bytes(chip: &Chip8) -> Result<Vec<u8>, SaveStateError> {
+    Ok(options().serialize(chip)?)
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Chip8, SaveStateError> {
+    Ok(options().deserialize(bytes)?)
+}
+
+/// Write the whole state of the machine to a file.
+pub fn save(chip: &Chip8, path: impl AsRef<Path>) -> Result<(), SaveStateError> {
+    fs::write(path, to_bytes(chip)?)?;
+    Ok(())
+}
+
+/// Read the whole state of the machine from a file.
+pub fn load(path: impl AsRef<Path>) -> Result<Chip8, SaveStateError> {
+    from_bytes(&fs::read(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn round_trip_restores_machine() -> Result<()> {
+        let mut chip = Chip8::default();
+        chip.load(&[0x61, 0x02, 0x71, 0x03]);
+        chip.advance_instruction()?;
+
+        assert_eq!(from_bytes(&to_bytes(&chip)?)?, chip);
+        Ok(())
+    }
+
+    #[rstest]
+    fn round_trip_keeps_waiting_for_key() -> Result<()> {
+        let mut chip = Chip8::default();
+        chip.load(&[0xF1, 0x0A, 0x71, 0x03]);
+        chip.advance_instruction()?;
+
+        let mut restored = from_bytes(&to_bytes(&chip)?)?;
+        restored.advance_instruction()?;
+
+        assert_eq!(restored, chip);
+
+        restored.press_key(0x4)?;
+        restored.unpress_key(0x4)?;
+
+        assert_eq!(restored.memory().v[1], 0x4);
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_bytes_rejects_corrupt_data() -> Result<()> {
+        assert!(matches!(
+            from_bytes(&[0xFF, 0x00, 0x12]),
+            Err(SaveStateError::Corrupt(_))
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    fn from_bytes_rejects_invalid_key_register() -> Result<()> {
+        let mut chip = Chip8::default();
+        chip.load(&[0xF1, 0x0A]);
+        chip.advance_instruction()?;
+        let mut bytes = to_bytes(&chip)?;
+        // The waiting register comes right before the wait counter and both draw stats, all 0.
+        let vx = bytes.len() - 6;
+        assert_eq!(bytes[vx..], [0x1, 0, 0, 0, 0, 0]);
+
+        bytes[vx] = 0x10;
+
+        assert!(matches!(
+            from_bytes(&bytes),
+            Err(SaveStateError::Corrupt(_))
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    fn load_reports_missing_file() -> Result<()> {
+        assert!(matches!(
+            load("this/save/state/does/not/exist.bin"),
+            Err(SaveStateError::Io(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use image::{ImageResult, Rgb, RgbImage};
+
+use crate::ui::{rgb, Theme};
+
+/// Where the screenshot is stored.
+pub const PATH: &str = "screenshot.png";
+
+/// How many image pixels are used for each display pixel.
+pub const SCALE: u32 = 10;
+
+/// Build a scaled image of the display.
+///
+/// # Arguments
+///
+/// * `frame` - Display in `[y][x]` format.
+/// * `theme` - Colors of lit and unlit pixels.
+/// * `scale` - How many image pixels are used for each display pixel.
+pub fn render<Row: AsRef<[bool]>>(frame: &[Row], theme: Theme, scale: u32) -> RgbImage {
+    let width = frame.first().map_or(0, |row| row.as_ref().len()) as u32;
+    let height = frame.len() as u32;
+    let (fg, bg) = (Rgb(rgb(theme.fg)), Rgb(rgb(theme.bg)));
+
+    RgbImage::from_fn(width * scale, height * scale, |x, y| {
+        if frame[(y / scale) as usize].as_ref()[(x / scale) as usize] {
+            fg
+        } else {
+            bg
+        }
+    })
+}
+
+/// Save a scaled image of the display as PNG.
+pub fn save<Row: AsRef<[bool]>>(
+    frame: &[Row],
+    theme: Theme,
+    path: impl AsRef<Path>,
+) -> ImageResult<()> {
+    render(frame, theme, SCALE).save_with_format(path, image::ImageFormat::Png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn render_scales_pixels(#[values(1, 3)] scale: u32) -> Result<()> {
+        let frame = [[true, false], [false, false]];
+        let theme = Theme::AMBER;
+
+        let image = render(&frame, theme, scale);
+
+        assert_eq!(image.dimensions(), (2 * scale, 2 * scale));
+        for (x, y, &pixel) in image.enumerate_pixels() {
+            let lit = x < scale && y < scale;
+            assert_eq!(
+                pixel,
+                Rgb(rgb(if lit { theme.fg } else { theme.bg })),
+                "pixel at {x} {y}"
+            );
+        }
+        Ok(())
+    }
+}
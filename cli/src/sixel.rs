@@ -0,0 +1,186 @@
+use std::io::{self, Write};
+
+use crossterm::{cursor, QueueableCommand};
+use ratatui::layout::Rect;
+
+use crate::ui::{rgb, Theme};
+
+/// How many image pixels are used for each display pixel when the terminal does not report its cell size.
+pub const SCALE: usize = 4;
+
+/// Rows of image pixels packed in each sixel.
+const BAND_HEIGHT: usize = 6;
+
+/// Whether the terminal is known to draw sixel graphics.
+///
+/// Terminals that support sixel do not announce it consistently,
+/// so this goes by `TERM` and `TERM_PROGRAM`.
+pub fn supported() -> bool {
+    supported_by(
+        std::env::var("TERM").ok().as_deref(),
+        std::env::var("TERM_PROGRAM").ok().as_deref(),
+    )
+}
+
+fn supported_by(term: Option<&str>, term_program: Option<&str>) -> bool {
+    const TERMS: [&str; 4] = ["foot", "mlterm", "yaft", "contour"];
+    const PROGRAMS: [&str; 3] = ["WezTerm", "mlterm", "iTerm.app"];
+
+    term.is_some_and(|term| term.contains("sixel") || TERMS.iter().any(|t| term.starts_with(t)))
+        || term_program.is_some_and(|program| PROGRAMS.contains(&program))
+}
+
+/// How many image pixels are used for each display pixel so the image covers the same cells as the half block display.
+///
+/// Falls back to [`SCALE`] when the terminal does not report its size in pixels.
+pub fn scale() -> usize {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => {
+            let cell_width = (size.width / size.columns) as usize;
+            let cell_height = (size.height / size.rows) as usize;
+            // Half blocks fit 1x2 pixels in a cell.
+            cell_width.min(cell_height / 2).max(1)
+        }
+        _ => SCALE,
+    }
+}
+
+/// Encode the display as a two color sixel image.
+///
+/// # Arguments
+///
+/// * `frame` - Display in `[y][x]` format.
+/// * `theme` - Colors of lit and unlit pixels.
+/// * `scale` - How many image pixels are used for each display pixel.
+pub fn encode<Row: AsRef<[bool]>>(frame: &[Row], theme: Theme, scale: usize) -> String {
+    let width = frame.first().map_or(0, |row| row.as_ref().len()) * scale;
+    let height = frame.len() * scale;
+    let lit = |x: usize, y: usize| frame[y / scale].as_ref()[x / scale];
+
+    let mut sixel = format!("\x1bPq\"1;1;{width};{height}");
+    // Register 0 holds unlit pixels, register 1 lit ones.
+    for (register, color) in [theme.bg, theme.fg].into_iter().enumerate() {
+        let [r, g, b] = rgb(color).map(|c| (c as usize * 100 + 127) / 255);
+        sixel += &format!("#{register};2;{r};{g};{b}");
+    }
+
+    let bands = (0..height).step_by(BAND_HEIGHT).map(|top| {
+        [false, true]
+            .into_iter()
+            .enumerate()
+            .map(|(register, color)| {
+                let columns = (0..width).map(|x| {
+                    let bits = (top..height.min(top + BAND_HEIGHT))
+                        .filter(|&y| lit(x, y) == color)
+                        .fold(0, |bits, y| bits | 1 << (y - top));
+                    char::from(b'?' + bits)
+                });
+                format!("#{register}{}", run_length(columns))
+            })
+            .collect::<Vec<_>>()
+            .join("$")
+    });
+    sixel += &bands.collect::<Vec<_>>().join("-");
+
+    sixel + "\x1b\\"
+}
+
+/// Collapse runs of the same sixel into repeat introducers.
+fn run_length(sixels: impl Iterator<Item = char>) -> String {
+    // A repeat takes at least 3 characters, so shorter runs are left as they are.
+    const MIN_RUN: usize = 4;
+
+    let mut encoded = String::new();
+    let mut flush = |sixel: char, count: usize| {
+        if count >= MIN_RUN {
+            encoded += &format!("!{count}{sixel}");
+        } else {
+            encoded.extend(std::iter::repeat(sixel).take(count));
+        }
+    };
+
+    let mut run: Option<(char, usize)> = None;
+    for sixel in sixels {
+        run = match run {
+            Some((current, count)) if current == sixel => Some((current, count + 1)),
+            Some((current, count)) => {
+                flush(current, count);
+                Some((sixel, 1))
+            }
+            None => Some((sixel, 1)),
+        };
+    }
+    if let Some((current, count)) = run {
+        flush(current, count);
+    }
+
+    encoded
+}
+
+/// Draw an encoded image with its top left corner in a cell, leaving the cursor where it was.
+pub fn draw(out: &mut impl Write, area: Rect, sixel: &str) -> io::Result<()> {
+    out.queue(cursor::SavePosition)?
+        .queue(cursor::MoveTo(area.x, area.y))?;
+    out.write_all(sixel.as_bytes())?;
+    out.queue(cursor::RestorePosition)?.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn encode_single_band() -> Result<()> {
+        let frame = [[true, false], [false, false]];
+
+        assert_eq!(
+            encode(&frame, Theme::MONO, 1),
+            "\x1bPq\"1;1;2;2#0;2;0;0;0#1;2;100;100;100#0AB$#1@?\x1b\\"
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn encode_scales_and_splits_bands() -> Result<()> {
+        let frame = [[false, true], [true, true]];
+
+        assert_eq!(
+            encode(&frame, Theme::AMBER, 4),
+            concat!(
+                "\x1bPq\"1;1;8;8#0;2;0;0;0#1;2;100;69;0",
+                // Rows 0 to 5, the left half is lit from row 4 down and the right half is all lit.
+                "#0!4N!4?$#1!4o!4~-",
+                // Rows 6 and 7, all lit.
+                "#0!8?$#1!8B",
+                "\x1b\\"
+            )
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_length_collapses_long_runs() -> Result<()> {
+        assert_eq!(run_length("???@@@@@A".chars()), "???!5@A");
+        assert_eq!(run_length("".chars()), "");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Some("foot"), None, true)]
+    #[case(Some("xterm-sixel"), None, true)]
+    #[case(Some("xterm-256color"), Some("WezTerm"), true)]
+    #[case(Some("xterm-256color"), None, false)]
+    #[case(None, Some("Apple_Terminal"), false)]
+    fn supported_by_environment(
+        #[case] term: Option<&str>,
+        #[case] term_program: Option<&str>,
+        #[case] expected: bool,
+    ) -> Result<()> {
+        assert_eq!(supported_by(term, term_program), expected);
+        Ok(())
+    }
+}
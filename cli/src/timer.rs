@@ -22,4 +22,41 @@ impl Timer {
     pub fn delta(&self) -> Duration {
         self.current_time - self.last_time
     }
+
+    /// Continue measuring after a pause.
+    /// The time spent paused is skipped so the next delta only covers the time since resuming.
+    pub fn resume(&mut self) {
+        let delta = self.delta();
+        self.current_time = Instant::now();
+        self.last_time = self.current_time - delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn resume_skips_paused_time() -> Result<()> {
+        let mut target = Timer::new();
+        thread::sleep(Duration::from_millis(5));
+        target.update();
+        let delta = target.delta();
+
+        thread::sleep(Duration::from_millis(50));
+        target.resume();
+
+        assert_eq!(target.delta(), delta);
+
+        target.update();
+
+        assert!(target.delta() < Duration::from_millis(50));
+        Ok(())
+    }
 }
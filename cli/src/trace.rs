@@ -0,0 +1,148 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use chip_8::{Chip8, InstructionError, Memory, StepOutcome};
+
+/// Log of every write to RAM and registers as `frame,pc,addr,value` lines, for `--trace-writes`.
+///
+/// Registers are written as `V0`-`VF` and `I`, writes that keep a register the same are not seen.
+pub struct WriteTrace<W: Write> {
+    out: W,
+    /// Timer updates since the trace started.
+    frame: usize,
+    /// Why the trace could not be written, nothing more is written after it.
+    pub error: Option<io::Error>,
+}
+
+impl WriteTrace<BufWriter<File>> {
+    /// Start a trace in a file, replacing it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> WriteTrace<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            frame: 0,
+            error: None,
+        }
+    }
+
+    /// Count a timer update, should be called along with [`Chip8::advance_timer`].
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Run instructions one by one like [`Chip8::tick_instructions`], writing down what each of them wrote.
+    /// If the trace can not be written, the instructions still run and the error is kept in [`WriteTrace::error`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InstructionError`] if an instruction did not execute correctly.
+    pub fn tick_instructions(
+        &mut self,
+        chip: &mut Chip8,
+        n: usize,
+    ) -> Result<StepOutcome, InstructionError> {
+        let mut outcome = StepOutcome::Blocked;
+        for _ in 0..n {
+            let (pc, i, v) = (chip.pc(), chip.i(), chip.memory().v);
+            let result = chip.tick_instructions(1);
+            if self.error.is_none() {
+                self.error = self.record(pc, i, &v, chip.memory()).err();
+            }
+            outcome = result?;
+            if outcome.is_hit() {
+                break;
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Write down the changes made by the instruction at `pc`, given the registers before it ran.
+    fn record(&mut self, pc: u16, i: u16, v: &[u8], memory: &Memory) -> io::Result<()> {
+        for (vx, (&old, &new)) in v.iter().zip(&memory.v).enumerate() {
+            if old != new {
+                writeln!(self.out, "{},{pc:#05X},V{vx:X},{new:#04X}", self.frame)?;
+            }
+        }
+        if memory.i != i {
+            writeln!(self.out, "{},{pc:#05X},I,{:#05X}", self.frame, memory.i)?;
+        }
+        for &address in &memory.written {
+            writeln!(
+                self.out,
+                "{},{pc:#05X},{address:#05X},{:#04X}",
+                self.frame, memory.ram[address as usize]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::Config;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn tick_instructions_records_writes() -> Result<()> {
+        let mut chip = Chip8::from_rom(
+            &[
+                0x60, 0x01, // Load 1 into register 0
+                0x61, 0x02, // Load 2 into register 1
+                0xA3, 0x00, // Point I at 0x300
+                0xF1, 0x55, // Store registers 0 and 1 at I
+                0x12, 0x08, // Loop
+            ],
+            Config::default(),
+        )?;
+        let mut out = Vec::new();
+        let mut target = WriteTrace::new(&mut out);
+
+        target.tick_instructions(&mut chip, 3)?;
+        target.next_frame();
+        target.tick_instructions(&mut chip, 3)?;
+
+        assert!(target.error.is_none());
+        assert_eq!(
+            String::from_utf8(out)?,
+            concat!(
+                "0,0x200,V0,0x01\n",
+                "0,0x202,V1,0x02\n",
+                "0,0x204,I,0x300\n",
+                "1,0x206,0x300,0x01\n",
+                "1,0x206,0x301,0x02\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn tick_instructions_stops_at_error() -> Result<()> {
+        let mut chip = Chip8::from_rom(
+            &[
+                0x60, 0x01, // Load 1 into register 0
+                0x01, 0x23, // Call a machine code routine
+            ],
+            Config::default(),
+        )?;
+        let mut out = Vec::new();
+        let mut target = WriteTrace::new(&mut out);
+
+        assert!(target.tick_instructions(&mut chip, 5).is_err());
+        assert_eq!(String::from_utf8(out)?, "0,0x200,V0,0x01\n");
+        Ok(())
+    }
+}
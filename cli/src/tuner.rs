@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+/// Keeps the emulation in real time when frames take longer than they should, like on slow terminals,
+/// by running the instructions and timer updates of the frames that were missed along with the next one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoTuner {
+    /// Length of a frame at the target rate, in seconds.
+    target: f64,
+    /// Smoothed length of the last frames in seconds, so a single slow frame does not cause a burst.
+    average: f64,
+    /// Part of a frame that is owed but was not run yet.
+    owed: f64,
+}
+
+impl AutoTuner {
+    /// How much each frame length moves the average, lower is steadier but slower to react.
+    const SMOOTHING: f64 = 0.25;
+    /// Most frames run at once, so a long stall is skipped instead of being caught up in a burst.
+    pub const MAX_FRAMES: usize = 4;
+
+    /// # Arguments
+    ///
+    /// * `target` - Length of a frame at the target rate.
+    pub fn new(target: Duration) -> Self {
+        Self {
+            target: target.as_secs_f64(),
+            average: target.as_secs_f64(),
+            owed: 0.0,
+        }
+    }
+
+    /// How many frames to run now to keep up, from 1 to [`AutoTuner::MAX_FRAMES`].
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - How long the last frame took.
+    pub fn frames(&mut self, delta: Duration) -> usize {
+        // A stall, like the terminal being suspended, would keep the average high for many frames.
+        let delta = delta
+            .as_secs_f64()
+            .min(self.target * Self::MAX_FRAMES as f64);
+        self.average += (delta - self.average) * Self::SMOOTHING;
+        self.owed = (self.owed + self.average / self.target).min(Self::MAX_FRAMES as f64);
+
+        let frames = (self.owed as usize).clamp(1, Self::MAX_FRAMES);
+        self.owed = (self.owed - frames as f64).max(0.0);
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    const TARGET: Duration = Duration::from_micros(16_667);
+
+    fn run(target: &mut AutoTuner, deltas: impl IntoIterator<Item = Duration>) -> Vec<usize> {
+        deltas
+            .into_iter()
+            .map(|delta| target.frames(delta))
+            .collect()
+    }
+
+    #[rstest]
+    fn frames_on_time_runs_one(#[values(TARGET, TARGET / 2)] delta: Duration) -> Result<()> {
+        let mut target = AutoTuner::new(TARGET);
+
+        assert_eq!(run(&mut target, [delta; 10]), [1; 10]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn frames_catches_up_on_slow_frames() -> Result<()> {
+        let mut target = AutoTuner::new(TARGET);
+
+        run(&mut target, [TARGET * 2; 30]);
+
+        assert_eq!(run(&mut target, [TARGET * 2; 4]), [2; 4]);
+
+        let frames: usize = run(&mut target, [TARGET * 3 / 2; 30]).iter().sum();
+
+        assert!((44..=46).contains(&frames));
+        Ok(())
+    }
+
+    #[rstest]
+    fn frames_smooths_single_slow_frame() -> Result<()> {
+        let mut target = AutoTuner::new(TARGET);
+
+        let frames = run(&mut target, [TARGET * 4].into_iter().chain([TARGET; 9]));
+
+        assert!(frames.iter().all(|&frames| frames <= 2));
+        assert_eq!(frames.iter().sum::<usize>(), 12);
+        Ok(())
+    }
+
+    #[rstest]
+    fn frames_clamps_long_stall() -> Result<()> {
+        let mut target = AutoTuner::new(TARGET);
+
+        let frames = run(&mut target, [Duration::from_secs(10); 3]);
+
+        assert!(frames.iter().all(|&frames| frames <= AutoTuner::MAX_FRAMES));
+        assert!(frames.iter().sum::<usize>() <= 2 * AutoTuner::MAX_FRAMES);
+        assert_eq!(run(&mut target, [TARGET; 30]).last(), Some(&1));
+        Ok(())
+    }
+}
@@ -1,17 +1,42 @@
-use core::panic;
-use std::{cell::RefCell, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter},
+    path::PathBuf,
+    time::Duration,
+};
 
-use chip_8::Chip8;
-use crossterm::event::{self, poll, Event, KeyCode, KeyEventKind};
-use ratatui::{layout::Flex, prelude::*};
+use chip_8::{disassemble, Checkpoint, Chip8, Instruction, InstructionError, Memory, StepOutcome};
+use crossterm::event::{
+    self, poll, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::{layout::Flex, prelude::*, widgets::Paragraph};
 
-use crate::timer::Timer;
+use crate::{
+    keymap::KeyMap,
+    playlist::{self, Playlist},
+    recorder::{self, Recorder},
+    rewind::Rewind,
+    rom::Header,
+    savestate, screenshot,
+    timer::Timer,
+    trace::WriteTrace,
+    tuner::AutoTuner,
+};
 
 use super::{
+    braille_display::BrailleDisplay,
+    colored,
     debug_screen::{Keypad, MemoryScreen},
+    disassembly::DisassemblyView,
+    help::Help,
+    hex_dump::HexDump,
+    phosphor::{Phosphor, PhosphorDisplay},
     pixel_display::PixelDisplay,
+    sixel_display::SixelDisplay,
     stats::{Stat, StatBias},
-    LayoutAlign, LayoutLinear, LayoutSizeError, WidgetSize,
+    LayoutAlign, LayoutLinear, LayoutOverlay, LayoutSizeError, Theme, WidgetSize,
 };
 
 #[derive(PartialEq, Eq, Default, Clone, Copy)]
@@ -22,54 +47,673 @@ pub enum AppState {
     End,
 }
 
+/// What the machine is doing, shown at the start of the status line.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Status {
+    Running,
+    Paused,
+    /// Blocked on `Fx0A`.
+    WaitingKey,
+    /// Stopped by an instruction that failed, with its error.
+    Halted(String),
+    /// Stopped by `00FD`.
+    Exited,
+}
+
+impl Status {
+    pub fn label(&self) -> String {
+        match self {
+            Status::Running => "RUNNING".to_string(),
+            Status::Paused => "PAUSED".to_string(),
+            Status::WaitingKey => "WAITING KEY".to_string(),
+            Status::Halted(error) => format!("HALTED ({error})"),
+            Status::Exited => "HALTED".to_string(),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Status::Running => Color::Green,
+            Status::Paused => Color::Yellow,
+            Status::WaitingKey => Color::Cyan,
+            Status::Halted(_) | Status::Exited => Color::Red,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Default, Clone, Copy)]
+pub enum DisplayMode {
+    /// 1x2 pixels per character using half blocks.
+    #[default]
+    HalfBlock,
+    /// 2x4 pixels per character using braille patterns.
+    Braille,
+}
+
+/// Frontend settings that do not affect emulation.
+pub struct AppOptions {
+    pub theme: Theme,
+    /// Whether anything is drawn with colors, otherwise only the default ones and text modifiers are used.
+    pub color: bool,
+    pub keys: KeyMap,
+    /// Whether the terminal reports key releases.
+    pub key_releases: bool,
+    /// How many times faster instructions run with turbo.
+    pub turbo_factor: usize,
+    /// How many frames can be rewound.
+    pub rewind_frames: usize,
+    /// Whether to start paused instead of running the program right away.
+    pub paused: bool,
+    /// Whether the half block display is drawn as a sixel image instead.
+    pub sixel: bool,
+    /// Whether the half block display uses a whole character for each pixel instead.
+    pub full_block: bool,
+    /// ROMs to switch between, the first one that loaded is already in the machine.
+    pub playlist: Playlist,
+    /// Whether switching back to a ROM continues it where it was left.
+    pub keep_state: bool,
+    /// Header to remove from the ROMs of the playlist.
+    pub skip_header: Option<Header>,
+    /// Where every write is logged, if it is.
+    pub write_trace: Option<WriteTrace<BufWriter<File>>>,
+    /// Whether the frames missed when drawing falls behind are run along with the next one.
+    pub auto_tune: bool,
+}
+
 pub struct App {
     pub(crate) chip: Chip8,
     pub(crate) state: AppState,
-    timer_instructions: Timer,
-    target_instructions: usize,
-    timer_frames: RefCell<Timer>,
+    pub(crate) theme: Theme,
+    /// Whether anything is drawn with colors, phosphor decay needs them to show up.
+    pub(crate) color: bool,
+    pub(crate) display_mode: DisplayMode,
+    /// Afterglow of the pixels when phosphor decay is on.
+    /// Only shows up with [`DisplayMode::HalfBlock`], braille dots share one color per character.
+    pub(crate) phosphor: Option<Phosphor>,
+    /// Whether [`DisplayMode::HalfBlock`] is drawn as a sixel image, without phosphor decay.
+    pub(crate) sixel: bool,
+    /// Whether [`DisplayMode::HalfBlock`] uses a whole character for each pixel, without phosphor decay.
+    pub(crate) full_block: bool,
+    /// Where the sixel image should be drawn after the last render, if it was shown.
+    sixel_area: Cell<Option<Rect>>,
+    /// Whether the key bindings are shown over the emulator.
+    pub(crate) help: bool,
+    pub(crate) hex_dump_offset: usize,
+    /// Address selected in the disassembly to run to, follows the program counter when unset.
+    pub(crate) cursor: Option<u16>,
+    pub(crate) keys: KeyMap,
+    /// Whether the terminal reports key releases.
+    /// Otherwise, each key press is held for [`App::KEY_HOLD_UPDATES`].
+    key_releases: bool,
+    /// Updates left until a key is released, when the terminal does not report releases.
+    key_holds: [usize; Memory::SIZE_KEYS],
+    /// Where the keypad was last rendered, to find the keys under the mouse.
+    pub(crate) keypad_area: Cell<Rect>,
+    /// Key held down with the mouse.
+    mouse_key: Option<u8>,
+    /// Whether instructions run [`App::turbo_factor`] times faster.
+    pub(crate) turbo: bool,
+    turbo_factor: usize,
+    /// States of the previous frames.
+    rewind: Rewind,
+    /// States before each instruction stepped through while paused.
+    steps: Rewind,
+    /// Result of the last user action to show in the status line.
+    pub(crate) message: Option<String>,
+    /// Why the machine halted, cleared when its state changes.
+    pub(crate) error: Option<InstructionError>,
+    /// Frames rendered since the recording started.
+    recorder: RefCell<Option<Recorder>>,
+    playlist: Playlist,
+    /// States of the ROMs switched away from, if they are kept.
+    kept_states: Option<HashMap<PathBuf, Checkpoint>>,
+    /// Where the state is saved and loaded, [`savestate::PATH`] unless changed in tests.
+    savestate_path: PathBuf,
+    skip_header: Option<Header>,
+    write_trace: Option<WriteTrace<BufWriter<File>>>,
+    /// How many instructions run each frame, without the turbo boost.
+    instructions_per_frame: usize,
+    timer_frames: Timer,
     target_frames: usize,
+    /// Keeps the emulation in real time when frames take too long, if it should.
+    tuner: Option<AutoTuner>,
+    /// How many frames the last update ran, more than 1 when the tuner catches up.
+    frames_run: usize,
 }
 
 impl App {
-    pub fn new(chip: Chip8, target_instructions: usize, target_frames: usize) -> Self {
+    const HEX_DUMP_BYTES_PER_ROW: usize = 8;
+    const HEX_DUMP_ROWS: usize = 8;
+    const DISASSEMBLY_ROWS: usize = 9;
+    const RECORDING_SECONDS: usize = 30;
+    const KEY_HOLD_UPDATES: usize = 10;
+    /// How many frames are stepped back with each press, so holding the key rewinds at around normal speed.
+    const REWIND_STEP: usize = 2;
+    /// How many steps can be undone.
+    const UNDO_STEPS: usize = 256;
+    /// How many instructions a step over runs before giving up on the subroutine returning.
+    const STEP_OVER_LIMIT: usize = 1_000_000;
+
+    pub fn new(
+        chip: Chip8,
+        options: AppOptions,
+        instructions_per_frame: usize,
+        target_frames: usize,
+    ) -> Self {
         Self {
+            hex_dump_offset: chip.program_start() as usize,
             chip,
-            state: AppState::default(),
-            timer_instructions: Timer::new(),
-            timer_frames: RefCell::new(Timer::new()),
-            target_instructions,
+            state: if options.paused {
+                AppState::Pause
+            } else {
+                AppState::InProgress
+            },
+            theme: options.theme,
+            color: options.color,
+            display_mode: DisplayMode::default(),
+            phosphor: None,
+            sixel: options.sixel,
+            full_block: options.full_block,
+            sixel_area: Cell::default(),
+            help: false,
+            cursor: None,
+            keys: options.keys,
+            key_releases: options.key_releases,
+            key_holds: [0; Memory::SIZE_KEYS],
+            keypad_area: Cell::default(),
+            mouse_key: None,
+            turbo: false,
+            turbo_factor: options.turbo_factor,
+            rewind: Rewind::new(options.rewind_frames),
+            steps: Rewind::new(Self::UNDO_STEPS),
+            message: None,
+            error: None,
+            recorder: RefCell::new(None),
+            playlist: options.playlist,
+            kept_states: options.keep_state.then(HashMap::new),
+            savestate_path: PathBuf::from(savestate::PATH),
+            skip_header: options.skip_header,
+            write_trace: options.write_trace,
+            instructions_per_frame,
+            timer_frames: Timer::new(),
             target_frames,
+            tuner: options
+                .auto_tune
+                .then(|| AutoTuner::new(Duration::from_secs_f64(1f64 / target_frames as f64))),
+            frames_run: 1,
         }
     }
 
+    /// Handle the input and run a frame worth of instructions, should be called once per frame.
     pub fn update(&mut self) {
-        self.timer_instructions.update();
-
-        if poll(Duration::ZERO).expect("can poll terminal events") {
-            if let Event::Key(key) = event::read().expect("can read events") {
-                match (key.kind, key.code) {
-                    (KeyEventKind::Press, KeyCode::Char('q')) => self.state = AppState::End,
-                    (KeyEventKind::Press, KeyCode::Char('p')) => {
-                        self.state = if self.state == AppState::InProgress {
-                            AppState::Pause
-                        } else {
-                            AppState::InProgress
-                        }
+        while poll(Duration::ZERO).expect("can poll terminal events") {
+            match event::read().expect("can read events") {
+                Event::Key(key) => self.handle_key(key),
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                _ => (),
+            }
+        }
+
+        self.run_frame();
+    }
+
+    /// Run a frame worth of instructions and update the timers, or several frames to catch up with the tuner.
+    /// Does nothing while paused, which includes being halted by an error, or after the program exits.
+    fn run_frame(&mut self) {
+        if !self.key_releases {
+            self.release_held_keys();
+        }
+
+        if self.state == AppState::Pause || self.chip.is_halted() {
+            return;
+        }
+        self.timer_frames.update();
+
+        self.frames_run = self
+            .tuner
+            .as_mut()
+            .map_or(1, |tuner| tuner.frames(self.timer_frames.delta()));
+        for _ in 0..self.frames_run {
+            self.tick_instructions(self.instructions_per_frame());
+            self.advance_timer();
+            if self.state == AppState::Pause || self.chip.is_halted() {
+                break;
+            }
+        }
+    }
+
+    /// Run instructions, pausing if one of them changes a watched location or reaches a breakpoint.
+    /// An instruction that fails halts the machine, see [`App::error`].
+    fn tick_instructions(&mut self, n: usize) -> StepOutcome {
+        self.error = None;
+        let result = match &mut self.write_trace {
+            Some(trace) => trace.tick_instructions(&mut self.chip, n),
+            None => self.chip.tick_instructions(n),
+        };
+        if let Some(e) = self
+            .write_trace
+            .as_mut()
+            .and_then(|trace| trace.error.take())
+        {
+            self.write_trace = None;
+            self.message = Some(format!("Stopped tracing writes: {e}"));
+        }
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.state = AppState::Pause;
+                self.error = Some(e);
+                return StepOutcome::Blocked;
+            }
+        };
+        match outcome {
+            StepOutcome::WatchHit(watch) => {
+                self.state = AppState::Pause;
+                self.message = Some(format!("{watch} changed"));
+            }
+            StepOutcome::BreakpointHit(address) => {
+                self.state = AppState::Pause;
+                self.message = Some(format!("Reached {address:#05X}"));
+            }
+            StepOutcome::Executed | StepOutcome::Blocked => {}
+        }
+        outcome
+    }
+
+    /// Run a single instruction while paused.
+    /// If the machine is waiting for its timers, update them by a frame instead.
+    fn step(&mut self) {
+        self.steps.push(self.chip.checkpoint());
+        if self.chip.is_waiting_for_timer() {
+            self.chip.advance_timer();
+        } else {
+            self.tick_instructions(1);
+        }
+    }
+
+    /// Run the whole subroutine if the next instruction calls one, then pause after it returns.
+    /// Behaves like [`App::step`] for any other instruction.
+    fn step_over(&mut self) {
+        let depth = self.chip.memory().stack.len();
+        let call = !self.chip.is_waiting_for_timer()
+            && disassemble(&self.chip.memory().ram, self.chip.pc())
+                .next()
+                .is_some_and(|d| matches!(d.instruction, Some(Instruction::SubroutineCall { .. })));
+
+        self.step();
+        if !call {
+            return;
+        }
+
+        for _ in 0..Self::STEP_OVER_LIMIT {
+            if self.chip.memory().stack.len() <= depth || self.chip.is_waiting_for_key() {
+                return;
+            }
+            if self.chip.is_waiting_for_timer() {
+                self.chip.advance_timer();
+            } else if self.tick_instructions(1).is_hit() || self.error.is_some() {
+                return;
+            }
+        }
+        self.message = Some(format!(
+            "Subroutine did not return after {} instructions",
+            Self::STEP_OVER_LIMIT
+        ));
+    }
+
+    /// Move the disassembly cursor by a number of 2-byte words, starting from the program counter.
+    fn move_cursor(&mut self, words: isize) {
+        let cursor = self.cursor.unwrap_or(self.chip.pc());
+        self.cursor = Some(
+            cursor
+                .saturating_add_signed(words as i16 * 2)
+                .min((self.chip.ram_size() - 2) as u16),
+        );
+    }
+
+    /// Continue the execution until the program counter reaches the cursor.
+    fn run_to_cursor(&mut self) {
+        let Some(cursor) = self.cursor.take() else {
+            return;
+        };
+        self.chip.add_breakpoint(cursor, true);
+        self.message = Some(format!("Running to {cursor:#05X}"));
+        self.toggle_pause();
+    }
+
+    /// Go back to the state before the last step.
+    fn undo_step(&mut self) {
+        if let Some(checkpoint) = self.steps.rewind(1) {
+            self.chip.restore(&checkpoint);
+            self.error = None;
+        }
+        self.message = Some(format!("Undoing, {} steps left", self.steps.len()));
+    }
+
+    /// Load the next or the previous ROM of the playlist, from scratch unless its state is kept.
+    /// Entries that can not be loaded are skipped.
+    fn switch_rom(&mut self, forward: bool) {
+        if self.playlist.len() < 2 {
+            self.message = Some("No other ROMs to switch to".to_string());
+            return;
+        }
+
+        if let (Some(states), Some(path)) = (&mut self.kept_states, self.playlist.current()) {
+            states.insert(path.to_path_buf(), self.chip.checkpoint());
+        }
+        let chip = &mut self.chip;
+        let kept_states = &self.kept_states;
+        let skip_header = self.skip_header;
+        let switch = self.playlist.advance(forward, |path| {
+            match kept_states.as_ref().and_then(|states| states.get(path)) {
+                Some(checkpoint) => chip.restore(checkpoint),
+                None => {
+                    let rom = playlist::read(path, io::empty(), skip_header)
+                        .map_err(|e| e.to_string())?;
+                    chip.try_load(&rom).map_err(|e| e.to_string())?;
+                }
+            }
+            Ok::<_, String>(())
+        });
+
+        let mut message = switch.skipped;
+        if switch.loaded.is_some() {
+            self.error = None;
+            self.rewind.clear();
+            self.steps.clear();
+            self.cursor = None;
+            self.hex_dump_offset = self.chip.program_start() as usize;
+            self.key_holds = [0; Memory::SIZE_KEYS];
+            self.mouse_key = None;
+            if self.phosphor.is_some() {
+                self.phosphor = Some(Phosphor::default());
+            }
+            message.push(format!(
+                "Playing {} ({}/{})",
+                self.playlist.current().expect("loaded entry").display(),
+                self.playlist.index() + 1,
+                self.playlist.len()
+            ));
+        }
+        self.message = Some(message.join("; "));
+    }
+
+    /// React to a key event from the terminal.
+    /// While the help is shown, the next key press only closes it.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.help {
+            if key.kind == KeyEventKind::Press {
+                self.help = false;
+            }
+            return;
+        }
+
+        match (key.kind, key.code) {
+            (KeyEventKind::Press, KeyCode::Esc) => self.state = AppState::End,
+            (KeyEventKind::Press, KeyCode::Char('?')) => self.help = true,
+            (KeyEventKind::Press, KeyCode::Char('p')) => self.toggle_pause(),
+            (KeyEventKind::Press, KeyCode::Char('[')) => self.switch_rom(false),
+            (KeyEventKind::Press, KeyCode::Char(']')) => self.switch_rom(true),
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Char('n'))
+                if self.state == AppState::Pause =>
+            {
+                self.step();
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Char('o'))
+                if self.state == AppState::Pause =>
+            {
+                self.step_over();
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Char('u'))
+                if self.state == AppState::Pause =>
+            {
+                self.undo_step();
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Char('+' | '=')) => {
+                self.change_instructions_per_frame(1);
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Char('-')) => {
+                self.change_instructions_per_frame(-1);
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Left)
+                if self.state == AppState::Pause =>
+            {
+                self.move_cursor(-1);
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Right)
+                if self.state == AppState::Pause =>
+            {
+                self.move_cursor(1);
+            }
+            (KeyEventKind::Press, KeyCode::Tab) if self.state == AppState::Pause => {
+                self.run_to_cursor();
+            }
+            (KeyEventKind::Press, KeyCode::Up) if self.state == AppState::Pause => {
+                self.hex_dump_offset = self
+                    .hex_dump_offset
+                    .saturating_sub(Self::HEX_DUMP_BYTES_PER_ROW);
+            }
+            (KeyEventKind::Press, KeyCode::Down) if self.state == AppState::Pause => {
+                self.hex_dump_offset = usize::min(
+                    self.hex_dump_offset + Self::HEX_DUMP_BYTES_PER_ROW,
+                    self.chip.ram_size() - Self::HEX_DUMP_BYTES_PER_ROW * Self::HEX_DUMP_ROWS,
+                );
+            }
+            (KeyEventKind::Press, KeyCode::F(5)) => {
+                self.message = Some(match savestate::save(&self.chip, &self.savestate_path) {
+                    Ok(()) => format!("Saved state to {}", self.savestate_path.display()),
+                    Err(e) => format!("Could not save state: {e}"),
+                });
+            }
+            (KeyEventKind::Press, KeyCode::F(9)) => {
+                self.message = Some(match savestate::load(&self.savestate_path) {
+                    Ok(chip) => {
+                        // Keeps the watches, breakpoints, history and seeded generator that are not saved.
+                        self.chip.restore(&chip.checkpoint());
+                        self.error = None;
+                        format!("Loaded state from {}", self.savestate_path.display())
                     }
-                    _ => (),
+                    Err(e) => format!("Could not load state: {e}"),
+                });
+            }
+            (KeyEventKind::Press, KeyCode::F(12)) => {
+                self.message = Some(
+                    match screenshot::save(&self.chip.frame_buffer(), self.theme, screenshot::PATH)
+                    {
+                        Ok(()) => format!("Saved screenshot to {}", screenshot::PATH),
+                        Err(e) => format!("Could not save screenshot: {e}"),
+                    },
+                );
+            }
+            (KeyEventKind::Press, KeyCode::F(10)) => {
+                let recording = self.recorder.borrow_mut().take();
+                self.message = Some(if let Some(recording) = recording {
+                    match recorder::save(
+                        recording.frames(),
+                        self.theme,
+                        self.target_frames,
+                        recorder::PATH,
+                    ) {
+                        Ok(()) => format!("Saved recording to {}", recorder::PATH),
+                        Err(e) => format!("Could not save recording: {e}"),
+                    }
+                } else {
+                    *self.recorder.borrow_mut() =
+                        Some(Recorder::new(self.target_frames * Self::RECORDING_SECONDS));
+                    "Recording, press F10 again to save".to_string()
+                });
+            }
+            (KeyEventKind::Press, KeyCode::Char(' ')) => {
+                self.turbo = !self.turbo;
+                self.message = Some(if self.turbo {
+                    format!("Turbo x{}, press Space again to stop", self.turbo_factor)
+                } else {
+                    "Turbo stopped".to_string()
+                });
+            }
+            (KeyEventKind::Press | KeyEventKind::Repeat, KeyCode::Backspace) => {
+                if let Some(checkpoint) = self.rewind.rewind(Self::REWIND_STEP) {
+                    self.chip.restore(&checkpoint);
+                    self.error = None;
+                }
+                self.message = Some(format!("Rewinding, {} frames left", self.rewind.len()));
+            }
+            (KeyEventKind::Press, KeyCode::F(2)) => {
+                self.display_mode = match self.display_mode {
+                    DisplayMode::HalfBlock => DisplayMode::Braille,
+                    DisplayMode::Braille => DisplayMode::HalfBlock,
+                }
+            }
+            (KeyEventKind::Press, KeyCode::F(3)) => {
+                self.phosphor = match self.phosphor {
+                    Some(_) => None,
+                    None => Some(Phosphor::default()),
+                };
+                self.message = Some(format!(
+                    "Phosphor decay {}",
+                    if self.phosphor.is_some() { "on" } else { "off" }
+                ));
+            }
+            (KeyEventKind::Press, KeyCode::F(4)) => {
+                let frozen = !self.chip.timers_frozen();
+                self.chip.set_timers_frozen(frozen);
+                self.message = Some(format!(
+                    "Timers {}",
+                    if frozen { "frozen" } else { "running" }
+                ));
+            }
+            (kind, code) => {
+                if let Some(chip_key) = self.keys.get(code) {
+                    self.handle_chip_key(chip_key, kind);
                 }
             }
         }
+    }
 
-        if let Err(e) = self.chip.advance_instruction() {
-            panic!("{}", e);
-        };
+    /// Release the keys whose hold time ran out, when the terminal does not report releases.
+    fn release_held_keys(&mut self) {
+        for key in 0..Memory::SIZE_KEYS {
+            if self.key_holds[key] == 1 {
+                self.chip
+                    .unpress_key(key as u8)
+                    .expect("key is in 0-F range");
+            }
+            self.key_holds[key] = self.key_holds[key].saturating_sub(1);
+        }
+    }
+
+    /// React to a mouse event from the terminal.
+    /// Holding the left button over the keypad presses the key under it.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(key) = Keypad::key_at(self.keypad_area.get(), mouse.column, mouse.row) {
+                    self.chip.press_key(key).expect("key is in 0-F range");
+                    self.mouse_key = Some(key);
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                if let Some(key) = self.mouse_key.take() {
+                    self.chip.unpress_key(key).expect("key is in 0-F range");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Press or release a key of the machine.
+    fn handle_chip_key(&mut self, key: u8, kind: KeyEventKind) {
+        match (kind, self.key_releases) {
+            (KeyEventKind::Press, _) => {
+                self.chip.press_key(key).expect("key is in 0-F range");
+                self.key_holds[key as usize] = Self::KEY_HOLD_UPDATES;
+            }
+            (KeyEventKind::Repeat, false) => {
+                self.key_holds[key as usize] = Self::KEY_HOLD_UPDATES;
+            }
+            (KeyEventKind::Release, true) => {
+                self.chip.unpress_key(key).expect("key is in 0-F range");
+            }
+            _ => (),
+        }
+    }
+
+    /// Stop or continue the execution.
+    /// Time spent paused is not counted by the stats.
+    fn toggle_pause(&mut self) {
+        self.state = if self.state == AppState::InProgress {
+            AppState::Pause
+        } else {
+            self.timer_frames.resume();
+            self.steps = Rewind::new(Self::UNDO_STEPS);
+            AppState::InProgress
+        }
+    }
+
+    /// Update the timers of the machine and remember its state, should be called once per frame.
+    /// Does nothing while paused.
+    pub fn advance_timer(&mut self) {
+        if self.state == AppState::Pause {
+            return;
+        }
+        self.chip.advance_timer();
+        if let Some(trace) = &mut self.write_trace {
+            trace.next_frame();
+        }
+        self.rewind.push(self.chip.checkpoint());
+        if let Some(phosphor) = &mut self.phosphor {
+            phosphor.update(&self.chip.frame_buffer());
+        }
+    }
+
+    /// Run more or less instructions each frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - How many instructions to add, the amount never drops below 1.
+    fn change_instructions_per_frame(&mut self, change: isize) {
+        self.instructions_per_frame = self
+            .instructions_per_frame
+            .saturating_add_signed(change)
+            .max(1);
+        self.message = Some(format!(
+            "Running {} instructions per frame",
+            self.instructions_per_frame
+        ));
+    }
+
+    /// How many instructions run each frame, including the turbo boost.
+    pub fn instructions_per_frame(&self) -> usize {
+        if self.turbo {
+            self.instructions_per_frame * self.turbo_factor
+        } else {
+            self.instructions_per_frame
+        }
+    }
+
+    /// How many instructions per second should run, including the turbo boost.
+    pub fn target_instructions(&self) -> usize {
+        self.instructions_per_frame() * self.target_frames
+    }
+
+    pub fn status(&self) -> Status {
+        match (&self.error, self.state) {
+            (Some(error), _) => Status::Halted(error.to_string()),
+            (None, _) if self.chip.is_halted() => Status::Exited,
+            (None, AppState::Pause) => Status::Paused,
+            (None, _) if self.chip.is_waiting_for_key() => Status::WaitingKey,
+            (None, _) => Status::Running,
+        }
     }
 
     pub fn state(&self) -> AppState {
         self.state
     }
+
+    /// Cells the sixel image should cover, if the last render showed it.
+    pub fn sixel_area(&self) -> Option<Rect> {
+        self.sixel_area.get()
+    }
 }
 
 pub struct AppWidget<'a> {
@@ -81,23 +725,28 @@ impl<'a> Widget for AppWidget<'a> {
     where
         Self: Sized,
     {
-        // TODO(nenikitov): This internal mutability automatically updates the timer since last rendered frame
-        // Maybe find a more elegant solution
-        self.app.timer_frames.borrow_mut().update();
+        if let Some(recorder) = self.app.recorder.borrow_mut().as_mut() {
+            recorder.push(self.app.chip.frame_buffer());
+        }
+        self.app.sixel_area.set(None);
 
         let ips = Stat {
             name: "IPS".to_string(),
-            value: 1f64 / self.app.timer_instructions.delta().as_secs_f64(),
-            target: self.app.target_instructions as f64,
+            value: (self.app.instructions_per_frame() * self.app.frames_run) as f64
+                / self.app.timer_frames.delta().as_secs_f64(),
+            target: self.app.target_instructions() as f64,
             bias: StatBias::HigherBetter,
             precision: Some(0),
+            color: self.app.color,
         };
         let ips_secs = Stat {
             name: "sec".to_string(),
-            value: self.app.timer_instructions.delta().as_secs_f64(),
-            target: 1f64 / self.app.target_instructions as f64,
+            value: self.app.timer_frames.delta().as_secs_f64()
+                / (self.app.instructions_per_frame() * self.app.frames_run) as f64,
+            target: 1f64 / self.app.target_instructions() as f64,
             bias: StatBias::LowerBetter,
             precision: Some(4),
+            color: self.app.color,
         };
         let ips_stats = LayoutLinear {
             direction: Direction::Vertical,
@@ -109,17 +758,19 @@ impl<'a> Widget for AppWidget<'a> {
 
         let fps = Stat {
             name: "FPS".to_string(),
-            value: 1f64 / &self.app.timer_frames.borrow().delta().as_secs_f64(),
+            value: 1f64 / self.app.timer_frames.delta().as_secs_f64(),
             target: self.app.target_frames as f64,
             bias: StatBias::HigherBetter,
             precision: Some(0),
+            color: self.app.color,
         };
         let fps_secs = Stat {
             name: "sec".to_string(),
-            value: self.app.timer_frames.borrow().delta().as_secs_f64(),
+            value: self.app.timer_frames.delta().as_secs_f64(),
             target: 1f64 / self.app.target_frames as f64,
             bias: StatBias::LowerBetter,
             precision: Some(4),
+            color: self.app.color,
         };
         let fps_stats = LayoutLinear {
             direction: Direction::Vertical,
@@ -129,9 +780,16 @@ impl<'a> Widget for AppWidget<'a> {
             spacing: 0,
         };
 
+        let draw_stats = self.app.chip.draw_stats();
+        let draw_stats = Paragraph::new(format!(
+            "Draws/s: {}\nHits/s: {}",
+            draw_stats.sprites * Chip8::FREQUENCY_TIMER_UPDATE,
+            draw_stats.collisions * Chip8::FREQUENCY_TIMER_UPDATE
+        ));
+
         let stats = LayoutLinear {
             direction: Direction::Horizontal,
-            children: vec![(&ips_stats, None), (&fps_stats, None)],
+            children: vec![(&ips_stats, None), (&fps_stats, None), (&draw_stats, None)],
             flex_main_axis: Some(Flex::SpaceBetween),
             flex_cross_axis: false,
             spacing: 0,
@@ -142,9 +800,38 @@ impl<'a> Widget for AppWidget<'a> {
             horizontal: Alignment::Left,
             vertical: Alignment::Center,
         };
+        let frame_buffer = self.app.chip.frame_buffer();
+        let half_block_display = PixelDisplay {
+            theme: self.app.theme,
+            color: self.app.color,
+            full_block: self.app.full_block,
+            display: frame_buffer.as_slice(),
+        };
+        let braille_display = BrailleDisplay {
+            theme: self.app.theme,
+            color: self.app.color,
+            display: frame_buffer.as_slice(),
+        };
+        let phosphor_display = self.app.phosphor.as_ref().map(|phosphor| PhosphorDisplay {
+            theme: self.app.theme,
+            display: &frame_buffer,
+            phosphor,
+        });
+        let sixel_display = SixelDisplay {
+            pixels: layout::Size::new(frame_buffer[0].len() as u16, frame_buffer.len() as u16),
+            area: &self.app.sixel_area,
+        };
         let screen = LayoutAlign {
-            child: &PixelDisplay {
-                display: self.app.chip.memory().vram.as_slice(),
+            child: match (self.app.display_mode, &phosphor_display) {
+                // The image would be drawn over the help.
+                (DisplayMode::HalfBlock, _) if self.app.sixel && !self.app.help => &sixel_display,
+                (DisplayMode::HalfBlock, Some(phosphor_display))
+                    if self.app.color && !self.app.full_block =>
+                {
+                    phosphor_display
+                }
+                (DisplayMode::HalfBlock, _) => &half_block_display,
+                (DisplayMode::Braille, _) => &braille_display,
             },
             horizontal: Alignment::Center,
             vertical: Alignment::Center,
@@ -155,6 +842,33 @@ impl<'a> Widget for AppWidget<'a> {
             vertical: Alignment::Center,
         };
 
+        let hex_dump = HexDump {
+            ram: &self.app.chip.memory().ram,
+            offset: self.app.hex_dump_offset,
+            bytes_per_row: App::HEX_DUMP_BYTES_PER_ROW,
+            rows: App::HEX_DUMP_ROWS,
+            pc: self.app.chip.memory().pc,
+            i: self.app.chip.memory().i,
+            written: &self.app.chip.memory().written,
+            color: self.app.color,
+        };
+
+        let disassembly = DisassemblyView {
+            ram: &self.app.chip.memory().ram,
+            pc: self.app.chip.memory().pc,
+            cursor: self.app.cursor,
+            rows: App::DISASSEMBLY_ROWS,
+            color: self.app.color,
+        };
+
+        let debug = LayoutLinear {
+            direction: Direction::Horizontal,
+            children: vec![(&hex_dump, None), (&disassembly, None)],
+            flex_main_axis: None,
+            flex_cross_axis: false,
+            spacing: 2,
+        };
+
         let emulator = LayoutLinear {
             direction: Direction::Horizontal,
             children: vec![
@@ -167,15 +881,666 @@ impl<'a> Widget for AppWidget<'a> {
             spacing: 2,
         };
 
+        let status = self.app.status();
+        let message = Paragraph::new(Line::from(vec![
+            Span::styled(
+                status.label(),
+                colored(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(status.color()),
+                    self.app.color,
+                ),
+            ),
+            Span::raw(" "),
+            Span::raw(
+                match (&self.app.message, self.app.chip.waiting_register()) {
+                    (Some(message), _) => message.clone(),
+                    (None, Some(vx)) => format!("Press a key to store it in V{vx:X}"),
+                    (None, None) => String::new(),
+                },
+            ),
+        ]));
+
+        let main = LayoutLinear {
+            direction: Direction::Vertical,
+            children: vec![
+                (&stats, None),
+                (&emulator, Some(Constraint::Fill(1))),
+                (&debug, None),
+                (&message, None),
+            ],
+            flex_main_axis: None,
+            flex_cross_axis: true,
+            spacing: 1,
+        };
+        let help = LayoutAlign {
+            child: &Help {
+                keys: &self.app.keys,
+                color: self.app.color,
+            },
+            horizontal: Alignment::Center,
+            vertical: Alignment::Center,
+        };
+
         LayoutSizeError {
-            child: &LayoutLinear {
-                direction: Direction::Vertical,
-                children: vec![(&stats, None), (&emulator, Some(Constraint::Fill(1)))],
-                flex_main_axis: None,
-                flex_cross_axis: true,
-                spacing: 1,
+            child: &LayoutOverlay {
+                children: if self.app.help {
+                    vec![&main, &help]
+                } else {
+                    vec![&main]
+                },
             },
+            color: self.app.color,
         }
         .render_sized(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chip_8::{Config, Watch};
+    use crossterm::event::KeyModifiers;
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[fixture]
+    fn target(#[default(true)] key_releases: bool, #[default(false)] paused: bool) -> App {
+        App::new(
+            Chip8::default(),
+            AppOptions {
+                theme: Theme::default(),
+                color: true,
+                keys: KeyMap::try_from(HashMap::from([('j', 0x5)])).expect("valid key map"),
+                key_releases,
+                turbo_factor: 4,
+                rewind_frames: 600,
+                paused,
+                sixel: false,
+                full_block: false,
+                playlist: Playlist::default(),
+                keep_state: false,
+                skip_header: None,
+                write_trace: None,
+                auto_tune: false,
+            },
+            10,
+            60,
+        )
+    }
+
+    #[rstest]
+    #[case::machine_code(&[
+        0x61, 0x02, // Load 2 into register 1
+        0x01, 0x23, // Call a machine code routine
+    ])]
+    #[case::empty_stack(&[
+        0x61, 0x02, // Load 2 into register 1
+        0x00, 0xEE, // Return without a subroutine call
+    ])]
+    fn tick_instructions_halts_on_error(mut target: App, #[case] rom: &[u8]) -> Result<()> {
+        let error = Chip8::from_rom(rom, Config::default())?
+            .tick_instructions(2)
+            .expect_err("the second instruction fails");
+        target.chip.load(rom);
+        target.advance_timer();
+
+        target.tick_instructions(2);
+
+        assert!(target.state == AppState::Pause);
+        assert_eq!(target.status().label(), format!("HALTED ({error})"));
+        assert_eq!(target.chip.register(0x1), Some(2));
+
+        target.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+
+        assert_eq!(target.error, None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn run_frame_stops_at_unknown_opcode(mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x61, 0x02, // Load 2 into register 1
+            0xFF, 0xFF, // Unknown
+        ]);
+
+        target.run_frame();
+        let halted = target.chip.checkpoint();
+        target.run_frame();
+
+        assert!(matches!(target.status(), Status::Halted(_)));
+        assert_eq!(target.chip.register(0x1), Some(2));
+        assert_eq!(target.chip.checkpoint(), halted);
+
+        target.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+        assert_eq!(
+            target.hex_dump_offset,
+            Memory::INDEX_PROGRAM_START as usize + App::HEX_DUMP_BYTES_PER_ROW
+        );
+        assert!(target.state() == AppState::End);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::running(false, &[0x12, 0x00], "RUNNING")]
+    #[case::paused(true, &[0x12, 0x00], "PAUSED")]
+    #[case::waiting_key(false, &[0xF0, 0x0A], "WAITING KEY")]
+    #[case::exited(false, &[0x00, 0xFD], "HALTED")]
+    fn status_label(
+        #[case] paused: bool,
+        #[case] rom: &[u8],
+        #[case] expected: &str,
+    ) -> Result<()> {
+        let mut target = target(true, paused);
+        target.chip.load(rom);
+        target.chip.tick_instructions(1)?;
+
+        assert_eq!(target.status().label(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_into_tiny_area(
+        target: App,
+        #[values(Rect::new(0, 0, 0, 0), Rect::new(0, 0, 1, 1), Rect::new(3, 2, 1, 1))] area: Rect,
+        #[values(DisplayMode::HalfBlock, DisplayMode::Braille)] display_mode: DisplayMode,
+        #[values(false, true)] help: bool,
+    ) -> Result<()> {
+        let mut target = target;
+        target.display_mode = display_mode;
+        target.help = help;
+        let mut buf = Buffer::empty(area);
+
+        AppWidget { app: &target }.render(area, &mut buf);
+        // Growing back after being too small
+        let area = Rect::new(0, 0, 200, 60);
+        let mut buf = Buffer::empty(area);
+        AppWidget { app: &target }.render(area, &mut buf);
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_presses_remapped_key(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+
+        assert_eq!(
+            target.chip.memory().keys,
+            core::array::from_fn(|key| key == 0x5)
+        );
+
+        target.handle_key(KeyEvent::new_with_kind(
+            KeyCode::Char('j'),
+            KeyModifiers::NONE,
+            KeyEventKind::Release,
+        ));
+
+        assert_eq!(target.chip.memory().keys, [false; Memory::SIZE_KEYS]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_closes_help_with_any_key(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
+
+        assert!(target.help);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+        assert!(!target.help);
+        assert!(target.state() == AppState::End);
+        assert_eq!(target.chip.memory().keys, [false; Memory::SIZE_KEYS]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_ignores_unbound_key(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.memory().keys, [false; Memory::SIZE_KEYS]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn release_held_keys_without_release_events(#[with(false)] mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+
+        for _ in 0..App::KEY_HOLD_UPDATES - 1 {
+            target.release_held_keys();
+        }
+
+        assert!(target.chip.memory().keys[0x5]);
+
+        target.release_held_keys();
+
+        assert!(!target.chip.memory().keys[0x5]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_toggles_turbo(mut target: App) -> Result<()> {
+        assert_eq!(target.instructions_per_frame(), 10);
+        assert_eq!(target.target_instructions(), 600);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+        assert_eq!(target.instructions_per_frame(), 40);
+        assert_eq!(target.target_instructions(), 2400);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+        assert_eq!(target.instructions_per_frame(), 10);
+        assert_eq!(target.target_instructions(), 600);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_changes_instructions_per_frame(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE));
+
+        assert_eq!(target.instructions_per_frame(), 11);
+
+        for _ in 0..20 {
+            target.handle_key(KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE));
+        }
+
+        assert_eq!(target.instructions_per_frame(), 1);
+        assert_eq!(
+            target.message.as_deref(),
+            Some("Running 1 instructions per frame")
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_rewinds(mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Jump back
+        ]);
+        for _ in 0..5 {
+            target.chip.tick_instructions(2)?;
+            target.advance_timer();
+        }
+
+        target.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+
+        assert_eq!(
+            target.chip.register(0x0),
+            Some(5 - App::REWIND_STEP as u8 + 1)
+        );
+        assert_eq!(target.rewind.len(), 5 - App::REWIND_STEP);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_switches_rom(
+        mut target: App,
+        #[values(false, true)] keep_state: bool,
+    ) -> Result<()> {
+        let dir = std::env::temp_dir();
+        let paths: Vec<_> = ["a", "empty", "b"]
+            .iter()
+            .map(|name| dir.join(format!("chip_8_switch_{keep_state}_{name}.ch8")))
+            .collect();
+        std::fs::write(&paths[0], [0x61, 0x02])?; // Load 2 into register 1
+        std::fs::write(&paths[1], [])?;
+        std::fs::write(&paths[2], [0x62, 0x03])?; // Load 3 into register 2
+        target.playlist = Playlist::new(paths.clone());
+        target.kept_states = keep_state.then(HashMap::new);
+        target.chip.load(&std::fs::read(&paths[0])?);
+        target.tick_instructions(1);
+        target.advance_timer();
+
+        target.handle_key(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE));
+        let message = target.message.clone();
+        target.handle_key(KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE));
+        for path in &paths {
+            std::fs::remove_file(path)?;
+        }
+
+        assert_eq!(
+            message,
+            Some(format!(
+                "Skipped {}: ROM is empty; Playing {} (3/3)",
+                paths[1].display(),
+                paths[2].display()
+            ))
+        );
+        assert_eq!(target.playlist.index(), 0);
+        assert_eq!(target.chip.peek(Memory::INDEX_PROGRAM_START), Some(0x61));
+        assert_eq!(
+            target.chip.register(0x1),
+            Some(if keep_state { 2 } else { 0 })
+        );
+        assert_eq!(target.rewind.len(), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_switches_without_playlist(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE));
+
+        assert_eq!(
+            target.message.as_deref(),
+            Some("No other ROMs to switch to")
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_pause_stops_timers(mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x60, 0x05, // Load 5 into register 0
+            0xF0, 0x15, // Set delay timer to it
+        ]);
+        target.chip.tick_instructions(2)?;
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        target.advance_timer();
+
+        assert_eq!(target.chip.dt(), 5);
+        assert_eq!(target.rewind.len(), 0);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        target.advance_timer();
+
+        assert_eq!(target.chip.dt(), 4);
+        assert_eq!(target.rewind.len(), 1);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_steps_while_paused(#[with(true, true)] mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x60, 0x01, // Load 1 into register 0
+            0xF0, 0x15, // Set delay timer to it
+            0x70, 0x01, // Add 1 to register 0
+        ]);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.dt(), 1);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.dt(), 0);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 6);
+        assert_eq!(target.chip.register(0x0), Some(2));
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_steps_over_subroutine(#[with(true, true)] mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x22, 0x06, // Call the subroutine
+            0x70, 0x10, // Add 16 to register 0
+            0x12, 0x04, // Loop forever
+            0x70, 0x01, // Subroutine: add 1 to register 0
+            0xF0, 0x15, // Set delay timer to it
+            0x00, 0xEE, // Return
+        ]);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 2);
+        assert_eq!(target.chip.register(0x0), Some(1));
+        assert_eq!(target.chip.dt(), 0);
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.register(0x0), Some(17));
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START);
+        assert_eq!(target.chip.register(0x0), Some(0));
+        Ok(())
+    }
+
+    #[rstest]
+    fn update_pauses_on_watch_hit(mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x71, 0x01, // Add 1 to register 1
+            0x12, 0x00, // Loop forever
+        ]);
+        target.chip.add_watch(Watch::Register(0x1))?;
+
+        target.tick_instructions(10);
+
+        assert!(target.state() == AppState::Pause);
+        assert_eq!(target.message.as_deref(), Some("V1 changed"));
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.register(0x0), Some(1));
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_load_state_keeps_watches(mut target: App) -> Result<()> {
+        target.savestate_path = std::env::temp_dir().join("chip_8_load_state_keeps_watches.bin");
+        target.chip.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x71, 0x01, // Add 1 to register 1
+            0x12, 0x00, // Loop forever
+        ]);
+        target.chip.add_watch(Watch::Register(0x1))?;
+
+        target.handle_key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE));
+        std::fs::remove_file(&target.savestate_path)?;
+
+        assert_eq!(
+            target.message,
+            Some(format!(
+                "Loaded state from {}",
+                target.savestate_path.display()
+            ))
+        );
+
+        target.tick_instructions(10);
+
+        assert!(target.state() == AppState::Pause);
+        assert_eq!(target.message.as_deref(), Some("V1 changed"));
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_load_state_keeps_history(mut target: App) -> Result<()> {
+        target.savestate_path = std::env::temp_dir().join("chip_8_load_state_keeps_history.bin");
+        target.chip = Chip8::builder()
+            .rom(&[
+                0x70, 0x01, // Add 1 to register 0
+                0x71, 0x01, // Add 1 to register 1
+            ])
+            .history(Chip8::HISTORY_CAPACITY)
+            .build()?;
+
+        target.handle_key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE));
+        std::fs::remove_file(&target.savestate_path)?;
+        target.tick_instructions(2);
+
+        assert_eq!(target.chip.history().len(), 2);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_load_state_keeps_seed() -> Result<()> {
+        let run = |name: &str| -> Result<Chip8> {
+            let mut target = target(true, false);
+            target.savestate_path = std::env::temp_dir().join(format!("chip_8_{name}.bin"));
+            target.chip = Chip8::builder()
+                .rom(&[
+                    0xC0, 0xFF, // Load a random number into register 0
+                    0xC1, 0xFF, // Load a random number into register 1
+                    0x12, 0x00, // Loop
+                ])
+                .rng(42)
+                .build()?;
+
+            target.tick_instructions(5);
+            target.handle_key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE));
+            target.tick_instructions(5);
+            target.handle_key(KeyEvent::new(KeyCode::F(9), KeyModifiers::NONE));
+            std::fs::remove_file(&target.savestate_path)?;
+            target.tick_instructions(5);
+
+            Ok(target.chip)
+        };
+
+        assert_eq!(
+            run("load_state_keeps_seed_a")?,
+            run("load_state_keeps_seed_b")?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_runs_to_cursor(#[with(true, true)] mut target: App) -> Result<()> {
+        target.keys = KeyMap::qwerty();
+        target.chip.load(&[
+            0x70, 0x01, // Add 1 to register 0
+            0x70, 0x01, // Add 1 to register 0
+            0x70, 0x01, // Add 1 to register 0
+            0x12, 0x00, // Loop forever
+        ]);
+
+        target.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        target.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+
+        assert!(target.state() == AppState::Pause);
+        assert!(target.chip.memory().keys[0xB]);
+
+        target.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+
+        assert!(target.state() == AppState::InProgress);
+        assert_eq!(target.cursor, None);
+
+        target.tick_instructions(10);
+
+        assert!(target.state() == AppState::Pause);
+        assert_eq!(target.message.as_deref(), Some("Reached 0x204"));
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.register(0x0), Some(2));
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        target.tick_instructions(10);
+
+        assert!(target.state() == AppState::InProgress);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_doesnt_step_while_running(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_mouse_holds_keypad_key(mut target: App) -> Result<()> {
+        target.keypad_area.set(Rect::new(10, 5, 4, 4));
+        let click = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        target.handle_mouse(click(MouseEventKind::Down(MouseButton::Left), 11, 6));
+
+        assert_eq!(
+            target.chip.memory().keys,
+            core::array::from_fn(|key| key == 0x5)
+        );
+
+        target.handle_mouse(click(MouseEventKind::Up(MouseButton::Left), 0, 0));
+
+        assert_eq!(target.chip.memory().keys, [false; Memory::SIZE_KEYS]);
+
+        target.handle_mouse(click(MouseEventKind::Down(MouseButton::Left), 0, 0));
+
+        assert_eq!(target.chip.memory().keys, [false; Memory::SIZE_KEYS]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_toggles_phosphor(mut target: App) -> Result<()> {
+        target.handle_key(KeyEvent::new(KeyCode::F(3), KeyModifiers::NONE));
+
+        assert_eq!(target.phosphor, Some(Phosphor::default()));
+
+        target.handle_key(KeyEvent::new(KeyCode::F(3), KeyModifiers::NONE));
+
+        assert_eq!(target.phosphor, None);
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_freezes_timers(mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x60, 0x05, // Load 5 into register 0
+            0xF0, 0x15, // Set delay timer to it
+        ]);
+        target.chip.tick_instructions(2)?;
+
+        target.handle_key(KeyEvent::new(KeyCode::F(4), KeyModifiers::NONE));
+        target.advance_timer();
+
+        assert_eq!(target.chip.dt(), 5);
+        assert_eq!(target.message.as_deref(), Some("Timers frozen"));
+
+        target.handle_key(KeyEvent::new(KeyCode::F(4), KeyModifiers::NONE));
+        target.advance_timer();
+
+        assert_eq!(target.chip.dt(), 4);
+        assert_eq!(target.message.as_deref(), Some("Timers running"));
+        Ok(())
+    }
+
+    #[rstest]
+    fn handle_key_undoes_steps(#[with(true, true)] mut target: App) -> Result<()> {
+        target.chip.load(&[
+            0x60, 0x01, // Load 1 into register 0
+            0xF0, 0x15, // Set delay timer to it
+            0x70, 0x01, // Add 1 to register 0
+        ]);
+        let start = target.chip.clone();
+
+        for _ in 0..4 {
+            target.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        }
+
+        assert_eq!(target.chip.register(0x0), Some(2));
+
+        target.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+
+        assert_eq!(target.chip.pc(), Memory::INDEX_PROGRAM_START + 4);
+        assert_eq!(target.chip.register(0x0), Some(1));
+
+        for _ in 0..4 {
+            target.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+        }
+
+        assert_eq!(target.chip, start);
+        assert_eq!(target.message.as_deref(), Some("Undoing, 0 steps left"));
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use ratatui::{layout::Size, prelude::*, widgets::*};
+use std::ops::Deref;
+
+use super::{colored, Theme, WidgetSize};
+
+/// Bit of a braille dot in `[y][x]` format.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Convert a block of pixels stored in `[y][x]` format into a braille character.
+fn braille(block: [[bool; 2]; 4]) -> char {
+    let mask = block
+        .iter()
+        .flatten()
+        .zip(DOTS.iter().flatten())
+        .filter(|(&pixel, _)| pixel)
+        .fold(0, |mask, (_, dot)| mask | dot);
+
+    char::from_u32(0x2800 + mask).expect("braille pattern is a valid character")
+}
+
+/// Display that packs 2x4 pixels in one braille character.
+pub struct BrailleDisplay<Outer: ?Sized, Inner>
+where
+    Outer: Deref<Target = [Inner]>,
+    Inner: AsRef<[bool]>,
+{
+    pub theme: Theme,
+    /// Whether the theme is used, otherwise the dots have the default colors.
+    pub color: bool,
+    pub display: Outer,
+}
+
+impl<Outer, Inner> BrailleDisplay<Outer, Inner>
+where
+    Outer: Deref<Target = [Inner]>,
+    Inner: AsRef<[bool]>,
+{
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.display
+            .get(y)
+            .and_then(|row| row.as_ref().get(x))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+impl<Outer, Inner> WidgetSize for BrailleDisplay<Outer, Inner>
+where
+    Outer: Deref<Target = [Inner]>,
+    Inner: AsRef<[bool]>,
+{
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let size = self.minimum_size();
+
+        let lines: Vec<Line> = (0..size.height as usize)
+            .map(|row| -> String {
+                (0..size.width as usize)
+                    .map(|column| {
+                        let mut block = [[false; 2]; 4];
+                        for (y, block_row) in block.iter_mut().enumerate() {
+                            for (x, pixel) in block_row.iter_mut().enumerate() {
+                                *pixel = self.pixel(column * 2 + x, row * 4 + y);
+                            }
+                        }
+                        braille(block)
+                    })
+                    .collect()
+            })
+            .map(Line::from)
+            .collect();
+
+        Paragraph::new(lines)
+            .style(colored(
+                Style::default().fg(self.theme.fg).bg(self.theme.bg),
+                self.color,
+            ))
+            .render(area, buf);
+
+        size
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: self
+                .display
+                .first()
+                .map_or(0, |row| row.as_ref().len().div_ceil(2)) as u16,
+            height: self.display.len().div_ceil(4) as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn braille_maps_empty_block() -> Result<()> {
+        assert_eq!(braille([[false; 2]; 4]), '⠀');
+        Ok(())
+    }
+
+    #[rstest]
+    fn braille_maps_full_block() -> Result<()> {
+        assert_eq!(braille([[true; 2]; 4]), '⣿');
+        Ok(())
+    }
+
+    #[rstest]
+    fn braille_maps_dots() -> Result<()> {
+        assert_eq!(
+            braille([[true, false], [false, true], [false, false], [true, false]]),
+            '\u{2851}'
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn minimum_size_packs_pixels() -> Result<()> {
+        let display = [[false; 64]; 32];
+
+        assert_eq!(
+            BrailleDisplay {
+                theme: Theme::default(),
+                color: true,
+                display: display.as_slice(),
+            }
+            .minimum_size(),
+            Size {
+                width: 32,
+                height: 8,
+            }
+        );
+        Ok(())
+    }
+}
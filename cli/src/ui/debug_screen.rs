@@ -1,3 +1,4 @@
+use chip_8::{Chip8, Config, Instruction, Memory};
 use ratatui::{layout::Size, prelude::*, widgets::*};
 
 use super::*;
@@ -35,6 +36,101 @@ impl<'a> WidgetSize for Registers<'a> {
     }
 }
 
+/// Value of the flag register `VF`, with what the last instruction that wrote it used it for.
+struct Flags<'a> {
+    chip: &'a Chip8,
+    color: bool,
+}
+
+impl<'a> Flags<'a> {
+    /// Meaning of `VF` after an instruction, `None` when the instruction leaves `VF` alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `instruction` - Instruction that was executed.
+    /// * `config` - Quirks it was executed with.
+    fn hint(instruction: &Instruction, config: &Config) -> Option<&'static str> {
+        match *instruction {
+            Instruction::AddVxWithVy { .. } => Some("carry"),
+            Instruction::SubtractVxWithVy { .. } | Instruction::SubtractVyWithVx { .. } => {
+                Some("no borrow")
+            }
+            Instruction::Shift1RightVxWithVy { .. } | Instruction::Shift1LeftVxWithVy { .. } => {
+                Some("shifted")
+            }
+            Instruction::DisplayDraw { .. } => Some("collision"),
+            Instruction::AddIWithVx { .. } if config.add_to_index_stores_overflow => {
+                Some("overflow")
+            }
+            Instruction::OrVxWithVy { .. }
+            | Instruction::AndVxWithVy { .. }
+            | Instruction::XorVxWithVy { .. }
+                if config.logic_resets_vf =>
+            {
+                Some("flag")
+            }
+            Instruction::SetVxWithValue { vx, .. }
+            | Instruction::AddVxValue { vx, .. }
+            | Instruction::SetVxWithVy { vx, .. }
+            | Instruction::OrVxWithVy { vx, .. }
+            | Instruction::AndVxWithVy { vx, .. }
+            | Instruction::XorVxWithVy { vx, .. }
+            | Instruction::SetVxWithRandom { vx, .. }
+            | Instruction::SetVxWithDt { vx }
+            | Instruction::SetVxWithNextPressedKeyBlocking { vx }
+            | Instruction::LoadRegistersUntil { vx }
+                if vx == Memory::INDEX_FLAG_REGISTER =>
+            {
+                Some("flag")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> WidgetSize for Flags<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let vf = self.chip.memory().v[Memory::INDEX_FLAG_REGISTER];
+        // Generic when nothing recorded wrote the flag, like when the history is not recorded.
+        let hint = self
+            .chip
+            .history()
+            .iter()
+            .rev()
+            .find_map(|(_, instruction)| Self::hint(instruction, self.chip.config()))
+            .unwrap_or("flag");
+
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw("vf "),
+                Span::styled(
+                    format!("{vf:02X}"),
+                    colored(
+                        Style::default()
+                            .add_modifier(Modifier::BOLD)
+                            .fg(Color::LightRed),
+                        self.color,
+                    ),
+                ),
+            ]),
+            Line::styled(
+                hint,
+                colored(Style::default().fg(Color::DarkGray), self.color),
+            ),
+        ])
+        .render(area, buf);
+
+        self.minimum_size()
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: 9,
+            height: 2,
+        }
+    }
+}
+
 pub struct MemoryScreen<'a> {
     pub app: &'a App,
 }
@@ -56,6 +152,11 @@ impl<'a> WidgetSize for MemoryScreen<'a> {
             spacing: 1,
         };
 
+        let flags = Flags {
+            chip: &self.app.chip,
+            color: self.app.color,
+        };
+
         let stack = self
             .app
             .chip
@@ -75,9 +176,12 @@ impl<'a> WidgetSize for MemoryScreen<'a> {
         let make_title = |title: &'a str| {
             Paragraph::new(Span::styled(
                 title,
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(Color::Magenta),
+                colored(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::Magenta),
+                    self.app.color,
+                ),
             ))
         };
 
@@ -86,23 +190,25 @@ impl<'a> WidgetSize for MemoryScreen<'a> {
             children: vec![
                 (&make_title("MEM"), None),
                 (
-                    &Paragraph::new(format!("pc {:04X}", self.app.chip.memory().pc)),
+                    &Paragraph::new(format!("pc {:04X}", self.app.chip.pc())),
                     None,
                 ),
                 (
-                    &Paragraph::new(format!("dt {:02X}", self.app.chip.memory().dt)),
+                    &Paragraph::new(format!("dt {:02X}", self.app.chip.dt())),
                     None,
                 ),
                 (
-                    &Paragraph::new(format!("st {:02X}", self.app.chip.memory().st)),
+                    &Paragraph::new(format!("st {:02X}", self.app.chip.st())),
                     None,
                 ),
                 (
-                    &Paragraph::new(format!("i  {:04X}", self.app.chip.memory().i)),
+                    &Paragraph::new(format!("i  {:04X}", self.app.chip.i())),
                     None,
                 ),
                 (&make_title("REG"), None),
                 (&registers, None),
+                (&make_title("FLAGS"), None),
+                (&flags, None),
                 (&make_title("STK"), None),
                 (&stack, None),
             ],
@@ -115,8 +221,8 @@ impl<'a> WidgetSize for MemoryScreen<'a> {
 
     fn minimum_size(&self) -> Size {
         Size {
-            width: 7,
-            height: 15 + self.app.chip.memory().stack.len() as u16,
+            width: 9,
+            height: 18 + self.app.chip.memory().stack.len() as u16,
         }
     }
 }
@@ -129,7 +235,12 @@ struct Key<'a> {
 impl<'a> WidgetSize for Key<'a> {
     fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
         let mut style = Style::default();
-        if self.app.chip.memory().keys[self.key] {
+        if self
+            .app
+            .chip
+            .pressed_keys()
+            .any(|key| key as usize == self.key)
+        {
             style = style.add_modifier(Modifier::REVERSED);
         }
 
@@ -177,44 +288,55 @@ impl<'a> WidgetSize for KeyRow<'a> {
     }
 }
 
+/// Arrangement of the keys on the original COSMAC VIP keypad.
+pub(super) const KEYPAD_LAYOUT: [[usize; 4]; 4] = [
+    [0x1, 0x2, 0x3, 0xC],
+    [0x4, 0x5, 0x6, 0xD],
+    [0x7, 0x8, 0x9, 0xE],
+    [0xA, 0x0, 0xB, 0xF],
+];
+
 pub struct Keypad<'a> {
     pub app: &'a App,
 }
 
+impl<'a> Keypad<'a> {
+    /// Key drawn at a terminal cell, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Where the keypad was rendered.
+    /// * `column` - Column of the cell.
+    /// * `row` - Row of the cell.
+    pub fn key_at(area: Rect, column: u16, row: u16) -> Option<u8> {
+        let x = column.checked_sub(area.x)?;
+        let y = row.checked_sub(area.y)?;
+        if x >= area.width || y >= area.height {
+            return None;
+        }
+
+        KEYPAD_LAYOUT
+            .get(y as usize)?
+            .get(x as usize)
+            .map(|&key| key as u8)
+    }
+}
+
 impl<'a> WidgetSize for Keypad<'a> {
     fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        self.app.keypad_area.set(area);
+
+        let rows: Vec<_> = KEYPAD_LAYOUT
+            .iter()
+            .map(|keys| KeyRow {
+                app: self.app,
+                keys: keys.to_vec(),
+            })
+            .collect();
+
         LayoutLinear {
             direction: Direction::Vertical,
-            children: vec![
-                (
-                    &KeyRow {
-                        app: self.app,
-                        keys: vec![0x1, 0x2, 0x3, 0xC],
-                    },
-                    None,
-                ),
-                (
-                    &KeyRow {
-                        app: self.app,
-                        keys: vec![0x4, 0x5, 0x6, 0xD],
-                    },
-                    None,
-                ),
-                (
-                    &KeyRow {
-                        app: self.app,
-                        keys: vec![0x7, 0x8, 0x9, 0xE],
-                    },
-                    None,
-                ),
-                (
-                    &KeyRow {
-                        app: self.app,
-                        keys: vec![0xA, 0x0, 0xB, 0xF],
-                    },
-                    None,
-                ),
-            ],
+            children: rows.iter().map(|r| (r as &dyn WidgetSize, None)).collect(),
             flex_main_axis: None,
             flex_cross_axis: false,
             spacing: 0,
@@ -229,3 +351,101 @@ impl<'a> WidgetSize for Keypad<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    const AREA: Rect = Rect {
+        x: 10,
+        y: 5,
+        width: 4,
+        height: 4,
+    };
+
+    #[rstest]
+    #[case::carry(&[0x60, 0xFF, 0x61, 0x01, 0x80, 0x14], "vf 01    carry    ")]
+    #[case::no_borrow(&[0x60, 0x01, 0x61, 0x02, 0x80, 0x15], "vf 00    no borrow")]
+    #[case::unrelated(&[0x6F, 0x07, 0x61, 0x01, 0x00, 0xE0], "vf 07    flag     ")]
+    #[case::carry_before_unrelated(
+        &[0x60, 0xFF, 0x61, 0x01, 0x80, 0x14, 0x62, 0x03, 0x00, 0xE0],
+        "vf 01    carry    "
+    )]
+    fn flags_render_value_and_hint(#[case] rom: &[u8], #[case] expected: &str) -> Result<()> {
+        let mut chip =
+            Chip8::from_rom(rom, Config::default())?.with_history(Chip8::HISTORY_CAPACITY);
+        chip.tick_instructions(rom.len() / 2)?;
+        let target = Flags {
+            chip: &chip,
+            color: true,
+        };
+        let area = Rect::new(0, 0, 9, 2);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.render_sized(area, &mut buf), area.as_size());
+        assert_eq!(
+            buf.content.iter().map(|c| c.symbol()).collect::<String>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(false, "vf 01    carry    ")]
+    #[case(true, "vf 01    overflow ")]
+    fn flags_hint_add_to_index(
+        #[case] add_to_index_stores_overflow: bool,
+        #[case] expected: &str,
+    ) -> Result<()> {
+        let rom = [
+            0x60, 0xFF, // Load 0xFF into register 0
+            0x61, 0x01, // Load 1 into register 1
+            0x80, 0x14, // Add register 1 to register 0, setting the carry
+            0xAF, 0xFF, // Point I at the last byte of RAM
+            0xF1, 0x1E, // Add register 1 to I, going past the end of RAM
+        ];
+        let config = Config {
+            add_to_index_stores_overflow,
+            ..Config::default()
+        };
+        let mut chip = Chip8::from_rom(&rom, config)?.with_history(Chip8::HISTORY_CAPACITY);
+        chip.tick_instructions(5)?;
+        let target = Flags {
+            chip: &chip,
+            color: true,
+        };
+        let area = Rect::new(0, 0, 9, 2);
+        let mut buf = Buffer::empty(area);
+        target.render_sized(area, &mut buf);
+
+        assert_eq!(
+            buf.content.iter().map(|c| c.symbol()).collect::<String>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn key_at_corners() -> Result<()> {
+        assert_eq!(Keypad::key_at(AREA, 10, 5), Some(0x1));
+        assert_eq!(Keypad::key_at(AREA, 13, 5), Some(0xC));
+        assert_eq!(Keypad::key_at(AREA, 10, 8), Some(0xA));
+        assert_eq!(Keypad::key_at(AREA, 13, 8), Some(0xF));
+        assert_eq!(Keypad::key_at(AREA, 11, 8), Some(0x0));
+        Ok(())
+    }
+
+    #[rstest]
+    fn key_at_outside(
+        #[values((9, 5), (14, 5), (10, 4), (10, 9), (0, 0))] position: (u16, u16),
+    ) -> Result<()> {
+        let (column, row) = position;
+
+        assert_eq!(Keypad::key_at(AREA, column, row), None);
+        Ok(())
+    }
+}
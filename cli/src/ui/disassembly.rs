@@ -0,0 +1,57 @@
+use chip_8::disassemble;
+use ratatui::{layout::Size, prelude::*, widgets::*};
+
+use super::WidgetSize;
+
+/// Decoded instructions around the program counter, or around the cursor when it is moved.
+pub struct DisassemblyView<'a> {
+    pub ram: &'a [u8],
+    pub pc: u16,
+    /// Address selected to run to.
+    pub cursor: Option<u16>,
+    pub rows: usize,
+    /// Whether the cursor is colored, otherwise it is underlined.
+    pub color: bool,
+}
+
+impl<'a> DisassemblyView<'a> {
+    const WIDTH: u16 = 22;
+}
+
+impl<'a> WidgetSize for DisassemblyView<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let start = self
+            .cursor
+            .unwrap_or(self.pc)
+            .saturating_sub((self.rows / 2 * 2) as u16);
+
+        let lines: Vec<Line> = disassemble(self.ram, start)
+            .take(self.rows)
+            .map(|d| {
+                let mut style = Style::default();
+                if d.address == self.pc {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                if Some(d.address) == self.cursor {
+                    style = if self.color {
+                        style.fg(Color::LightYellow)
+                    } else {
+                        style.add_modifier(Modifier::UNDERLINED)
+                    };
+                }
+                Line::styled(d.to_string(), style)
+            })
+            .collect();
+
+        Paragraph::new(lines).render(area, buf);
+
+        self.minimum_size()
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: Self::WIDTH,
+            height: self.rows as u16,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use ratatui::{layout::Size, prelude::*, widgets::*};
+
+use crate::keymap::KeyMap;
+
+use super::{colored, debug_screen::KEYPAD_LAYOUT, WidgetSize};
+
+/// Key bindings of the frontend, in `(keys, action)` format.
+const BINDINGS: [(&str, &str); 20] = [
+    ("Esc", "Quit"),
+    ("?", "Show this help"),
+    ("p", "Pause or continue"),
+    ("[ ]", "Switch to the previous or next ROM"),
+    ("n", "Step, while paused"),
+    ("o", "Step over a call, while paused"),
+    ("u", "Undo a step, while paused"),
+    ("+ -", "Change speed"),
+    ("Space", "Toggle turbo"),
+    ("Backspace", "Rewind"),
+    ("Up Down", "Scroll memory, while paused"),
+    ("Left Right", "Move the cursor, while paused"),
+    ("Tab", "Run to the cursor, while paused"),
+    ("F2", "Switch display mode"),
+    ("F3", "Toggle phosphor decay"),
+    ("F4", "Freeze or unfreeze the timers"),
+    ("F5", "Save state"),
+    ("F9", "Load state"),
+    ("F10", "Start or save a recording"),
+    ("F12", "Save a screenshot"),
+];
+
+/// Popup listing the key bindings and the keypad mapping, closed by any key.
+pub struct Help<'a> {
+    pub keys: &'a KeyMap,
+    /// Whether the titles and keys are colored.
+    pub color: bool,
+}
+
+impl<'a> Help<'a> {
+    /// Width of the column with the keys.
+    const KEY_WIDTH: usize = 11;
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let title = |title: &'static str| {
+            Line::styled(
+                title,
+                colored(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::Magenta),
+                    self.color,
+                ),
+            )
+        };
+
+        let mut lines = vec![title("KEYS")];
+        lines.extend(BINDINGS.iter().map(|&(keys, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{keys:<0$}", Self::KEY_WIDTH),
+                    colored(Style::default().fg(Color::LightYellow), self.color),
+                ),
+                Span::raw(action),
+            ])
+        }));
+
+        lines.push(Line::default());
+        lines.push(title("KEYPAD"));
+        lines.extend(KEYPAD_LAYOUT.iter().map(|row| {
+            Line::from(
+                row.iter()
+                    .flat_map(|&key| {
+                        let host = self.keys.host(key as u8).unwrap_or(' ');
+                        [
+                            Span::styled(
+                                format!("{host} "),
+                                colored(Style::default().fg(Color::LightYellow), self.color),
+                            ),
+                            Span::raw(format!("{key:X}   ")),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        }));
+
+        lines
+    }
+}
+
+impl<'a> WidgetSize for Help<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        Clear.render(area, buf);
+        Paragraph::new(self.lines())
+            .block(
+                Block::bordered()
+                    .title_top(Line::from(" Help ").centered())
+                    .title_bottom(Line::from(" Press any key to close ").centered())
+                    .border_type(BorderType::Rounded),
+            )
+            .render(area, buf);
+
+        self.minimum_size()
+    }
+
+    fn minimum_size(&self) -> Size {
+        let lines = self.lines();
+
+        Size {
+            width: lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2,
+            height: lines.len() as u16 + 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn lines_map_keypad() -> Result<()> {
+        let keys = KeyMap::qwerty();
+        let target = Help {
+            keys: &keys,
+            color: true,
+        };
+
+        let lines = target.lines();
+        let keypad: Vec<_> = lines[lines.len() - 4..]
+            .iter()
+            .map(|line| line.to_string().trim_end().to_string())
+            .collect();
+
+        assert_eq!(
+            keypad,
+            vec![
+                "1 1   2 2   3 3   4 C",
+                "q 4   w 5   e 6   r D",
+                "a 7   s 8   d 9   f E",
+                "z A   x 0   c B   v F",
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_fits_minimum_size() -> Result<()> {
+        let keys = KeyMap::qwerty();
+        let target = Help {
+            keys: &keys,
+            color: true,
+        };
+        let size = target.minimum_size();
+        let area = Rect::new(0, 0, size.width, size.height);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.render_sized(area, &mut buf), area.as_size());
+        Ok(())
+    }
+}
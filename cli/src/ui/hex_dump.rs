@@ -0,0 +1,184 @@
+use ratatui::{layout::Size, prelude::*, widgets::*};
+
+use super::{colored, WidgetSize};
+
+/// Window into RAM displayed as rows of hexadecimal bytes.
+pub struct HexDump<'a> {
+    pub ram: &'a [u8],
+    /// Address of the first displayed byte.
+    pub offset: usize,
+    pub bytes_per_row: usize,
+    pub rows: usize,
+    /// Address of the current instruction.
+    pub pc: u16,
+    /// Address stored in the index register.
+    pub i: u16,
+    /// Addresses written since the last update.
+    pub written: &'a [u16],
+    /// Whether the highlights are colored, otherwise they use reverse video, underline, and bold.
+    pub color: bool,
+}
+
+impl<'a> HexDump<'a> {
+    fn style(&self, address: usize) -> Style {
+        let pc = address == self.pc as usize || address == self.pc as usize + 1;
+        let i = address == self.i as usize;
+        let written = self.written.contains(&(address as u16));
+        match (self.color, pc, i, written) {
+            (true, true, _, _) => Style::default().fg(Color::Black).bg(Color::LightYellow),
+            (true, _, true, _) => Style::default().fg(Color::Black).bg(Color::LightCyan),
+            (true, _, _, true) => Style::default().fg(Color::LightRed),
+            (false, true, _, _) => Style::default().add_modifier(Modifier::REVERSED),
+            (false, _, true, _) => Style::default().add_modifier(Modifier::UNDERLINED),
+            (false, _, _, true) => Style::default().add_modifier(Modifier::BOLD),
+            _ => Style::default(),
+        }
+    }
+}
+
+impl<'a> WidgetSize for HexDump<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let lines: Vec<Line> = (0..self.rows)
+            .map(|row| self.offset + row * self.bytes_per_row)
+            .filter(|&start| start < self.ram.len())
+            .map(|start| {
+                let mut spans = vec![Span::styled(
+                    format!("{start:04X}:"),
+                    colored(Style::default().fg(Color::DarkGray), self.color),
+                )];
+                for (address, byte) in self.ram[start..]
+                    .iter()
+                    .enumerate()
+                    .take(self.bytes_per_row)
+                    .map(|(i, byte)| (start + i, byte))
+                {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(format!("{byte:02X}"), self.style(address)));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        Paragraph::new(lines).render(area, buf);
+
+        self.minimum_size()
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: (5 + 3 * self.bytes_per_row) as u16,
+            height: self.rows as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn minimum_size_fits_rows() -> Result<()> {
+        let ram = [0; 64];
+        let dump = HexDump {
+            ram: &ram,
+            offset: 0,
+            bytes_per_row: 8,
+            rows: 4,
+            pc: 0,
+            i: 0,
+            written: &[],
+            color: true,
+        };
+
+        assert_eq!(
+            dump.minimum_size(),
+            Size {
+                width: 29,
+                height: 4,
+            }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_writes_rows() -> Result<()> {
+        let ram: Vec<u8> = (0..32).collect();
+        let dump = HexDump {
+            ram: &ram,
+            offset: 8,
+            bytes_per_row: 4,
+            rows: 2,
+            pc: 0,
+            i: 0,
+            written: &[],
+            color: true,
+        };
+        let area = Rect::new(0, 0, 17, 2);
+        let mut buf = Buffer::empty(area);
+
+        dump.render_sized(area, &mut buf);
+
+        assert_eq!(
+            buf.content.iter().map(|c| c.symbol()).collect::<String>(),
+            "0008: 08 09 0A 0B000C: 0C 0D 0E 0F"
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_highlights_written_bytes() -> Result<()> {
+        let ram = [0; 8];
+        let dump = HexDump {
+            ram: &ram,
+            offset: 0,
+            bytes_per_row: 8,
+            rows: 1,
+            pc: 0,
+            i: 2,
+            written: &[2, 5],
+            color: true,
+        };
+        let area = Rect::new(0, 0, 29, 1);
+        let mut buf = Buffer::empty(area);
+
+        dump.render_sized(area, &mut buf);
+
+        // Index register takes precedence
+        assert_eq!(buf.get(12, 0).bg, Color::LightCyan);
+        assert_eq!(buf.get(21, 0).fg, Color::LightRed);
+        assert_eq!(buf.get(18, 0).fg, Color::Reset);
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_highlights_without_color() -> Result<()> {
+        let ram = [0; 8];
+        let dump = HexDump {
+            ram: &ram,
+            offset: 0,
+            bytes_per_row: 8,
+            rows: 1,
+            pc: 0,
+            i: 2,
+            written: &[5],
+            color: false,
+        };
+        let area = Rect::new(0, 0, 29, 1);
+        let mut buf = Buffer::empty(area);
+
+        dump.render_sized(area, &mut buf);
+
+        assert_eq!(buf.get(6, 0).modifier, Modifier::REVERSED);
+        assert_eq!(buf.get(12, 0).modifier, Modifier::UNDERLINED);
+        assert_eq!(buf.get(21, 0).modifier, Modifier::BOLD);
+        assert!(buf
+            .content
+            .iter()
+            .all(|c| c.fg == Color::Reset && c.bg == Color::Reset));
+        Ok(())
+    }
+}
@@ -1,38 +1,79 @@
 mod app;
+mod braille_display;
 mod debug_screen;
+mod disassembly;
+mod help;
+mod hex_dump;
+mod phosphor;
 mod pixel_display;
+mod sixel_display;
 mod size_error;
 mod stats;
+mod theme;
 mod widget;
 
 use crossterm::{
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
     ExecutableCommand,
 };
 use ratatui::prelude::*;
-use std::io::{self, stdout, Stdout};
+use std::io::{self, stdout, Stdout, Write};
+
+use crate::crash;
 
 pub use app::*;
+pub use theme::*;
 pub use widget::*;
 
+/// Whether the terminal can report key releases.
+pub fn supports_key_releases() -> bool {
+    supports_keyboard_enhancement().unwrap_or(false)
+}
+
 pub fn start_ui() -> Result<Terminal<CrosstermBackend<Stdout>>, io::Error> {
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
+    stdout().execute(EnableMouseCapture)?;
+    if supports_key_releases() {
+        stdout().execute(PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::REPORT_EVENT_TYPES,
+        ))?;
+    }
 
     Terminal::new(CrosstermBackend::new(stdout()))
 }
 
 pub fn end_ui() -> Result<(), io::Error> {
+    if supports_key_releases() {
+        stdout().execute(PopKeyboardEnhancementFlags)?;
+    }
+    stdout().execute(DisableMouseCapture)?;
     disable_raw_mode()?;
     stdout().execute(LeaveAlternateScreen)?;
     Ok(())
 }
 
+/// Write the state of the machine to [`crash::PATH`] and restore the terminal before printing the panic.
 pub fn panic_hook() {
     let original_hook = std::panic::take_hook();
 
     std::panic::set_hook(Box::new(move |panic| {
+        let dumped = crash::dump(panic);
         end_ui().unwrap();
         original_hook(panic);
+        if dumped {
+            let _ = writeln!(
+                io::stderr(),
+                "The state of the emulator was written to {}, please attach it to the bug report",
+                crash::PATH
+            );
+        }
     }))
 }
@@ -0,0 +1,129 @@
+use chip_8::Memory;
+use ratatui::{layout::Size, prelude::*, widgets::*};
+
+use super::{pixel_display::half_block, Theme, WidgetSize};
+use crate::recorder::Frame;
+
+/// Afterglow of the pixels that turned off, like the slow phosphor of old CRT displays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phosphor {
+    /// Frames left until each pixel fades out completely, stored in `[y][x]` format.
+    glow: [[u8; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT],
+}
+
+impl Default for Phosphor {
+    fn default() -> Self {
+        Self {
+            glow: [[0; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT],
+        }
+    }
+}
+
+impl Phosphor {
+    /// How many frames a pixel takes to fade out after turning off.
+    pub const FADE_FRAMES: u8 = 4;
+
+    /// Light up the lit pixels and dim the others, should be called once per frame.
+    pub fn update(&mut self, frame: &Frame) {
+        for (glow_row, frame_row) in self.glow.iter_mut().zip(frame) {
+            for (glow, &lit) in glow_row.iter_mut().zip(frame_row) {
+                *glow = if lit {
+                    Self::FADE_FRAMES
+                } else {
+                    glow.saturating_sub(1)
+                };
+            }
+        }
+    }
+
+    /// Color of a pixel, lit pixels are always fully bright.
+    fn color(&self, theme: Theme, frame: &Frame, x: usize, y: usize) -> Color {
+        if frame[y][x] {
+            theme.fg
+        } else {
+            theme.fade(self.glow[y][x], Self::FADE_FRAMES + 1)
+        }
+    }
+}
+
+/// Same as [`super::pixel_display::PixelDisplay`], but pixels fade out over a few frames instead of turning off instantly.
+pub struct PhosphorDisplay<'a> {
+    pub theme: Theme,
+    pub display: &'a Frame,
+    pub phosphor: &'a Phosphor,
+}
+
+impl<'a> WidgetSize for PhosphorDisplay<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let lines: Vec<Line> = (0..Memory::SIZE_DISPLAY_HEIGHT)
+            .step_by(2)
+            .map(|y| -> Vec<Span> {
+                (0..Memory::SIZE_DISPLAY_WIDTH)
+                    .map(|x| {
+                        half_block(
+                            self.phosphor.color(self.theme, self.display, x, y),
+                            self.phosphor.color(self.theme, self.display, x, y + 1),
+                        )
+                    })
+                    .collect()
+            })
+            .map(Line::from)
+            .collect();
+
+        Paragraph::new(lines).render(area, buf);
+
+        self.minimum_size()
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: Memory::SIZE_DISPLAY_WIDTH as u16,
+            height: (Memory::SIZE_DISPLAY_HEIGHT / 2) as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    const BLANK: Frame = [[false; Memory::SIZE_DISPLAY_WIDTH]; Memory::SIZE_DISPLAY_HEIGHT];
+
+    #[rstest]
+    fn update_fades_unlit_pixels() -> Result<()> {
+        let mut target = Phosphor::default();
+        let mut frame = BLANK;
+        frame[3][5] = true;
+
+        target.update(&frame);
+
+        assert_eq!(target.glow[3][5], Phosphor::FADE_FRAMES);
+        assert_eq!(target.color(Theme::MONO, &frame, 5, 3), Color::White);
+
+        for frames in 1..=Phosphor::FADE_FRAMES {
+            target.update(&BLANK);
+
+            assert_eq!(target.glow[3][5], Phosphor::FADE_FRAMES - frames);
+        }
+        assert_eq!(target.color(Theme::MONO, &BLANK, 5, 3), Color::Black);
+        Ok(())
+    }
+
+    #[rstest]
+    fn color_dims_recently_unlit_pixels() -> Result<()> {
+        let mut target = Phosphor::default();
+        let mut frame = BLANK;
+        frame[0][0] = true;
+        target.update(&frame);
+
+        assert_eq!(
+            target.color(Theme::MONO, &BLANK, 0, 0),
+            Color::Rgb(204, 204, 204)
+        );
+        Ok(())
+    }
+}
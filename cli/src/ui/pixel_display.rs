@@ -1,12 +1,30 @@
 use ratatui::{layout::Size, prelude::*, widgets::*};
 use std::{iter, ops::Deref};
 
-use super::WidgetSize;
+use super::{colored, Theme, WidgetSize};
 
-fn generate_style(top: bool, bottom: bool) -> Style {
-    Style::default()
-        .fg(if top { Color::White } else { Color::Black })
-        .bg(if bottom { Color::White } else { Color::Black })
+/// Character showing two pixels stacked on top of each other.
+pub(super) fn half_block(top: Color, bottom: Color) -> Span<'static> {
+    Span::styled("▀", Style::default().fg(top).bg(bottom))
+}
+
+/// Character showing two pixels stacked on top of each other without colors,
+/// using the default ones for the lit half and reverse video when both are lit.
+fn half_block_plain(top: bool, bottom: bool) -> Span<'static> {
+    match (top, bottom) {
+        (true, true) => Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+        (true, false) => Span::raw("▀"),
+        (false, true) => Span::raw("▄"),
+        (false, false) => Span::raw(" "),
+    }
+}
+
+fn pixel_color(theme: Theme, lit: bool) -> Color {
+    if lit {
+        theme.fg
+    } else {
+        theme.bg
+    }
 }
 
 pub struct PixelDisplay<Outer: ?Sized, Inner>
@@ -14,29 +32,77 @@ where
     Outer: Deref<Target = [Inner]>,
     Inner: AsRef<[bool]>,
 {
+    pub theme: Theme,
+    /// Whether the theme is used, otherwise the pixels are drawn with [`half_block_plain`].
+    pub color: bool,
+    /// Whether each pixel takes a whole character instead of half of one,
+    /// for terminals where half blocks are far from square.
+    pub full_block: bool,
     pub display: Outer,
 }
 
+impl<Outer, Inner> PixelDisplay<Outer, Inner>
+where
+    Outer: Deref<Target = [Inner]>,
+    Inner: AsRef<[bool]>,
+{
+    /// One line for each row of pixels, drawn with `█` and spaces.
+    fn full_block_lines(&self) -> Vec<Line<'static>> {
+        let style = colored(
+            Style::default().fg(self.theme.fg).bg(self.theme.bg),
+            self.color,
+        );
+        self.display
+            .iter()
+            .map(|row| {
+                Line::from(
+                    row.as_ref()
+                        .iter()
+                        .map(|&lit| Span::styled(if lit { "█" } else { " " }, style))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+}
+
 impl<Outer, Inner> WidgetSize for PixelDisplay<Outer, Inner>
 where
     Outer: Deref<Target = [Inner]>,
     Inner: AsRef<[bool]>,
 {
     fn render_sized(&self, area: Rect, buf: &mut Buffer) -> layout::Size {
+        if self.full_block {
+            Paragraph::new(self.full_block_lines()).render(area, buf);
+            return self.minimum_size();
+        }
+
         let lines: Vec<Line> = self
             .display
-            .array_chunks::<2>()
-            .map(|[row_1, row_2]| iter::zip(row_1.as_ref(), row_2.as_ref()))
-            .map(|row_pairs| -> Vec<Span> {
-                row_pairs
-                    .map(|(top, bottom)| Span::styled("▀", generate_style(*top, *bottom)))
+            .chunks(2)
+            .map(|rows| -> Vec<Span> {
+                let top = rows[0].as_ref();
+                // The last row of an odd height display has blank pixels below it.
+                let bottom = rows.get(1).map_or(&[][..], |row| row.as_ref());
+                top.iter()
+                    .zip(bottom.iter().chain(iter::repeat(&false)))
+                    .map(|(&top, &bottom)| {
+                        if self.color {
+                            half_block(
+                                pixel_color(self.theme, top),
+                                pixel_color(self.theme, bottom),
+                            )
+                        } else {
+                            half_block_plain(top, bottom)
+                        }
+                    })
                     .collect()
             })
             .map(Line::from)
             .collect();
 
         let size = Size {
-            width: lines[0].width() as u16,
+            width: lines.first().map_or(0, Line::width) as u16,
             height: lines.len() as u16,
         };
         Paragraph::new(lines).render(area, buf);
@@ -46,8 +112,190 @@ where
 
     fn minimum_size(&self) -> Size {
         Size {
-            width: self.display[0].as_ref().len() as u16,
-            height: (self.display.len() / 2) as u16,
+            width: self.display.first().map_or(0, |row| row.as_ref().len()) as u16,
+            height: if self.full_block {
+                self.display.len()
+            } else {
+                self.display.len().div_ceil(2)
+            } as u16,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn render_sized_empty_display() -> Result<()> {
+        let display: [[bool; 64]; 0] = [];
+        let target = PixelDisplay {
+            theme: Theme::default(),
+            color: true,
+            full_block: false,
+            display: display.as_slice(),
+        };
+        let area = Rect::new(0, 0, 4, 4);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.minimum_size(), Size::default());
+        assert_eq!(target.render_sized(area, &mut buf), Size::default());
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_single_row() -> Result<()> {
+        let display = [[true, false]];
+        let target = PixelDisplay {
+            theme: Theme::AMBER,
+            color: true,
+            full_block: false,
+            display: display.as_slice(),
+        };
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.minimum_size(), Size::new(2, 1));
+        assert_eq!(target.render_sized(area, &mut buf), Size::new(2, 1));
+
+        let mut result = Buffer::empty(area);
+        result.set_line(
+            0,
+            0,
+            &Line::from(vec![
+                half_block(Theme::AMBER.fg, Theme::AMBER.bg),
+                half_block(Theme::AMBER.bg, Theme::AMBER.bg),
+            ]),
+            2,
+        );
+        assert_eq!(buf, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_three_rows() -> Result<()> {
+        let display = [[true, false], [false, true], [true, true]];
+        let target = PixelDisplay {
+            theme: Theme::MONO,
+            color: true,
+            full_block: false,
+            display: display.as_slice(),
+        };
+        let area = Rect::new(0, 0, 2, 2);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.minimum_size(), Size::new(2, 2));
+        assert_eq!(target.render_sized(area, &mut buf), Size::new(2, 2));
+
+        let (fg, bg) = (Theme::MONO.fg, Theme::MONO.bg);
+        let mut result = Buffer::empty(area);
+        result.set_line(
+            0,
+            0,
+            &Line::from(vec![half_block(fg, bg), half_block(bg, fg)]),
+            2,
+        );
+        result.set_line(
+            0,
+            1,
+            &Line::from(vec![half_block(fg, bg), half_block(fg, bg)]),
+            2,
+        );
+        assert_eq!(buf, result);
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_into_tiny_area(
+        #[values(Rect::new(0, 0, 0, 0), Rect::new(0, 0, 1, 1))] area: Rect,
+    ) -> Result<()> {
+        let display = [[true; 64]; 32];
+        let target = PixelDisplay {
+            theme: Theme::MONO,
+            color: true,
+            full_block: false,
+            display: display.as_slice(),
+        };
+        let mut buf = Buffer::empty(area);
+
+        target.render_sized(area, &mut buf);
+
+        if area.area() > 0 {
+            assert_eq!(buf.get(0, 0).symbol(), "▀");
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_without_color() -> Result<()> {
+        let display = [[true, true, false, false], [true, false, true, false]];
+        let target = PixelDisplay {
+            theme: Theme::AMBER,
+            color: false,
+            full_block: false,
+            display: display.as_slice(),
+        };
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+
+        target.render_sized(area, &mut buf);
+
+        assert_eq!(
+            buf.content.iter().map(|c| c.symbol()).collect::<String>(),
+            " ▀▄ "
+        );
+        for x in 0..4 {
+            assert_eq!(buf.get(x, 0).fg, Color::Reset);
+            assert_eq!(buf.get(x, 0).bg, Color::Reset);
+        }
+        assert_eq!(buf.get(0, 0).modifier, Modifier::REVERSED);
+        assert_eq!(buf.get(1, 0).modifier, Modifier::empty());
+        Ok(())
+    }
+
+    #[rstest]
+    fn minimum_size_full_block_doubles_height() -> Result<()> {
+        let display = [[false; 64]; 32];
+        let mut target = PixelDisplay {
+            theme: Theme::MONO,
+            color: true,
+            full_block: false,
+            display: display.as_slice(),
+        };
+
+        assert_eq!(target.minimum_size(), Size::new(64, 16));
+
+        target.full_block = true;
+
+        assert_eq!(target.minimum_size(), Size::new(64, 32));
+        Ok(())
+    }
+
+    #[rstest]
+    fn render_sized_full_block(#[values(true, false)] color: bool) -> Result<()> {
+        let display = [[true, false], [false, true], [true, true]];
+        let target = PixelDisplay {
+            theme: Theme::AMBER,
+            color,
+            full_block: true,
+            display: display.as_slice(),
+        };
+        let area = Rect::new(0, 0, 2, 3);
+        let mut buf = Buffer::empty(area);
+
+        assert_eq!(target.render_sized(area, &mut buf), Size::new(2, 3));
+        assert_eq!(
+            buf.content.iter().map(|c| c.symbol()).collect::<String>(),
+            "█  ███"
+        );
+        assert_eq!(
+            buf.get(0, 0).fg,
+            if color { Theme::AMBER.fg } else { Color::Reset }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use ratatui::{layout::Size, prelude::*, widgets::*};
+use std::cell::Cell;
+
+use super::WidgetSize;
+
+/// Blank space of the same size as the half block display, that a sixel image is drawn over after rendering.
+pub struct SixelDisplay<'a> {
+    /// Width and height of the display in pixels.
+    pub pixels: Size,
+    /// Where the space was last rendered.
+    pub area: &'a Cell<Option<Rect>>,
+}
+
+impl<'a> WidgetSize for SixelDisplay<'a> {
+    fn render_sized(&self, area: Rect, buf: &mut Buffer) -> Size {
+        let size = self.minimum_size();
+
+        Clear.render(area, buf);
+        self.area.set(Some(Rect {
+            width: size.width,
+            height: size.height,
+            ..area
+        }));
+
+        size
+    }
+
+    fn minimum_size(&self) -> Size {
+        Size {
+            width: self.pixels.width,
+            height: self.pixels.height.div_ceil(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn render_records_area() -> Result<()> {
+        let area = Cell::new(None);
+        let target = SixelDisplay {
+            pixels: Size::new(64, 32),
+            area: &area,
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, 20));
+
+        target.render_sized(Rect::new(3, 2, 70, 18), &mut buf);
+
+        assert_eq!(area.get(), Some(Rect::new(3, 2, 64, 16)));
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use ratatui::{layout::*, prelude::*, widgets::*};
 
-use super::{LayoutAlign, LayoutOverlay, WidgetSize};
+use super::{colored, LayoutAlign, LayoutOverlay, WidgetSize};
 
 pub struct SizeError {
     pub min: Size,
+    /// Whether the dimensions are colored by whether they are enough.
+    pub color: bool,
 }
 
 impl WidgetSize for SizeError {
@@ -18,19 +20,25 @@ impl WidgetSize for SizeError {
             let paragraph = Paragraph::new(vec![
                 Line::styled(
                     format!("Width = {} (needed {})", area.width, self.min.width),
-                    Style::default().fg(if lack_width {
-                        Color::LightRed
-                    } else {
-                        Color::LightGreen
-                    }),
+                    colored(
+                        Style::default().fg(if lack_width {
+                            Color::LightRed
+                        } else {
+                            Color::LightGreen
+                        }),
+                        self.color,
+                    ),
                 ),
                 Line::styled(
                     format!("Height = {} (needed {})", area.height, self.min.height),
-                    Style::default().fg(if lack_height {
-                        Color::LightRed
-                    } else {
-                        Color::LightGreen
-                    }),
+                    colored(
+                        Style::default().fg(if lack_height {
+                            Color::LightRed
+                        } else {
+                            Color::LightGreen
+                        }),
+                        self.color,
+                    ),
                 ),
             ]);
 
@@ -38,7 +46,10 @@ impl WidgetSize for SizeError {
                 .title_top(
                     Line::styled(
                         "Terminal window is too small",
-                        Style::default().white().add_modifier(Modifier::BOLD),
+                        colored(
+                            Style::default().white().add_modifier(Modifier::BOLD),
+                            self.color,
+                        ),
                     )
                     .centered(),
                 )
@@ -54,7 +65,7 @@ impl WidgetSize for SizeError {
                     },
                 )
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Red));
+                .border_style(colored(Style::default().fg(Color::Red), self.color));
 
             LayoutOverlay {
                 children: vec![
@@ -1,6 +1,6 @@
 use ratatui::{layout::Size, prelude::*, widgets::*};
 
-use super::WidgetSize;
+use super::{colored, WidgetSize};
 
 pub enum StatBias {
     HigherBetter,
@@ -13,6 +13,8 @@ pub struct Stat {
     pub target: f64,
     pub bias: StatBias,
     pub precision: Option<usize>,
+    /// Whether the value is colored by how close it is to the target.
+    pub color: bool,
 }
 
 impl Stat {
@@ -48,7 +50,7 @@ impl WidgetSize for Stat {
         };
 
         Paragraph::new(self.format())
-            .style(Style::default().fg(color))
+            .style(colored(Style::default().fg(color), self.color))
             .render(area, buf);
 
         self.minimum_size()
@@ -0,0 +1,123 @@
+use ratatui::prelude::*;
+
+/// Colors used to draw the pixel display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Color of lit pixels.
+    pub fg: Color,
+    /// Color of unlit pixels.
+    pub bg: Color,
+}
+
+impl Theme {
+    pub const MONO: Self = Self {
+        fg: Color::White,
+        bg: Color::Black,
+    };
+    pub const AMBER: Self = Self {
+        fg: Color::Rgb(255, 176, 0),
+        bg: Color::Black,
+    };
+    pub const GREEN: Self = Self {
+        fg: Color::Rgb(51, 255, 51),
+        bg: Color::Black,
+    };
+}
+
+impl Theme {
+    /// Color between the background and the foreground.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - How close to the foreground, `0` is the background.
+    /// * `levels` - Level that is the foreground.
+    pub fn fade(&self, level: u8, levels: u8) -> Color {
+        match level {
+            0 => self.bg,
+            level if level >= levels => self.fg,
+            level => {
+                let [fg, bg] = [rgb(self.fg), rgb(self.bg)];
+                let [r, g, b] = core::array::from_fn(|i| {
+                    let (fg, bg) = (fg[i] as i32, bg[i] as i32);
+                    (bg + (fg - bg) * level as i32 / levels as i32) as u8
+                });
+                Color::Rgb(r, g, b)
+            }
+        }
+    }
+}
+
+/// Style as given when colors are on, otherwise only its modifiers like bold and reverse video,
+/// so the terminal draws it with its default colors.
+pub fn colored(style: Style, color: bool) -> Style {
+    if color {
+        style
+    } else {
+        Style {
+            fg: None,
+            bg: None,
+            ..style
+        }
+    }
+}
+
+/// Approximate RGB value of a terminal color.
+pub fn rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Red => [128, 0, 0],
+        Color::Green => [0, 128, 0],
+        Color::Yellow => [128, 128, 0],
+        Color::Blue => [0, 0, 128],
+        Color::Magenta => [128, 0, 128],
+        Color::Cyan => [0, 128, 128],
+        Color::Gray => [192, 192, 192],
+        Color::DarkGray => [128, 128, 128],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [0, 0, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        _ => [0, 0, 0],
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::MONO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    #[rstest]
+    fn fade_blends_colors() -> Result<()> {
+        assert_eq!(Theme::AMBER.fade(0, 4), Color::Black);
+        assert_eq!(Theme::AMBER.fade(2, 4), Color::Rgb(127, 88, 0));
+        assert_eq!(Theme::AMBER.fade(4, 4), Theme::AMBER.fg);
+        Ok(())
+    }
+
+    #[rstest]
+    fn colored_keeps_only_modifiers() -> Result<()> {
+        let style = Style::default()
+            .fg(Color::Red)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD);
+
+        assert_eq!(colored(style, true), style);
+        assert_eq!(
+            colored(style, false),
+            Style::default().add_modifier(Modifier::BOLD)
+        );
+        Ok(())
+    }
+}
@@ -33,17 +33,18 @@ impl<'a> WidgetSize for LayoutAlign<'a> {
                 .y
                 .saturating_add((area.height.saturating_sub(child_size.height)) / 2),
             Alignment::Right => area
-                .x
+                .y
                 .saturating_add(area.height)
                 .saturating_sub(child_size.height),
         };
 
+        // A child larger than the area is clipped, so it does not draw outside the buffer.
         self.child.render_sized(
             Rect {
                 x,
                 y,
-                width: child_size.width,
-                height: child_size.height,
+                width: child_size.width.min(area.width),
+                height: child_size.height.min(area.height),
             },
             buf,
         );
@@ -192,6 +193,8 @@ impl<'a> WidgetSize for LayoutOverlay<'a> {
 
 pub struct LayoutSizeError<'a> {
     pub child: &'a dyn WidgetSize,
+    /// Whether the error is colored.
+    pub color: bool,
 }
 
 impl<'a> WidgetSize for LayoutSizeError<'a> {
@@ -202,7 +205,11 @@ impl<'a> WidgetSize for LayoutSizeError<'a> {
         if size_area.width >= size_minimum.width && size_area.height >= size_minimum.height {
             self.child.render_sized(area, buf)
         } else {
-            SizeError { min: size_minimum }.render_sized(area, buf)
+            SizeError {
+                min: size_minimum,
+                color: self.color,
+            }
+            .render_sized(area, buf)
         }
     }
 
@@ -224,3 +231,90 @@ impl<'a> WidgetSize for &'a Paragraph<'_> {
         Size { width, height }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    use eyre::Result;
+    use rstest::*;
+    use similar_asserts::assert_eq;
+
+    struct Probe {
+        size: Size,
+        area: Cell<Option<Rect>>,
+    }
+
+    impl WidgetSize for Probe {
+        fn render_sized(&self, area: Rect, _buf: &mut Buffer) -> Size {
+            self.area.set(Some(area));
+            self.size
+        }
+
+        fn minimum_size(&self) -> Size {
+            self.size
+        }
+    }
+
+    #[rstest]
+    fn layout_align_right_right_positions_at_bottom_right() -> Result<()> {
+        let area = Rect {
+            x: 10,
+            y: 2,
+            width: 30,
+            height: 8,
+        };
+        let mut buf = Buffer::empty(area);
+        let probe = Probe {
+            size: Size {
+                width: 4,
+                height: 3,
+            },
+            area: Cell::new(None),
+        };
+
+        LayoutAlign {
+            child: &probe,
+            horizontal: Alignment::Right,
+            vertical: Alignment::Right,
+        }
+        .render_sized(area, &mut buf);
+
+        assert_eq!(
+            probe.area.get(),
+            Some(Rect {
+                x: 36,
+                y: 7,
+                width: 4,
+                height: 3,
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn layout_align_clips_child_larger_than_area(
+        #[values(Rect::new(0, 0, 0, 0), Rect::new(0, 0, 1, 1), Rect::new(5, 5, 2, 1))] area: Rect,
+    ) -> Result<()> {
+        let mut buf = Buffer::empty(area);
+        let probe = Probe {
+            size: Size {
+                width: 4,
+                height: 3,
+            },
+            area: Cell::new(None),
+        };
+
+        LayoutAlign {
+            child: &probe,
+            horizontal: Alignment::Center,
+            vertical: Alignment::Center,
+        }
+        .render_sized(area, &mut buf);
+
+        assert_eq!(probe.area.get(), Some(area));
+        Ok(())
+    }
+}